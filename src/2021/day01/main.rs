@@ -0,0 +1,106 @@
+use std::{
+    collections::VecDeque,
+    error::Error,
+    fs::File,
+    io::{BufRead, BufReader},
+};
+
+// Windows of k numbers ending at i and i - 1 share k - 1 numbers, so the sum
+// grows exactly when x[i] > x[i - k]. Yields the index i of every increase.
+fn increases<R: BufRead>(
+    reader: R,
+    k: usize,
+) -> impl Iterator<Item = Result<usize, Box<dyn Error>>> {
+    let mut window = VecDeque::new();
+
+    reader.lines().enumerate().filter_map(move |(idx, line)| {
+        let number = match line.map(|l| l.trim().parse::<i64>()) {
+            Ok(Ok(number)) => number,
+            Ok(Err(e)) => return Some(Err(e.into())),
+            Err(e) => return Some(Err(e.into())),
+        };
+
+        window.push_back(number);
+        if window.len() <= k {
+            return None;
+        }
+
+        match window.pop_front() {
+            Some(first) if number > first => Some(Ok(idx)),
+            _ => None,
+        }
+    })
+}
+
+fn count<R: BufRead>(reader: R, k: usize, report: bool) -> Result<usize, Box<dyn Error>> {
+    let mut result = 0;
+
+    for idx in increases(reader, k) {
+        let idx = idx?;
+        if report {
+            println!("window {}: increase at {}", k, idx);
+        }
+        result += 1;
+    }
+
+    Ok(result)
+}
+
+pub(crate) fn solve(input: &str) -> Result<(usize, usize), Box<dyn Error>> {
+    Ok((
+        count(input.as_bytes(), 1, false)?,
+        count(input.as_bytes(), 3, false)?,
+    ))
+}
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let args = std::env::args().collect::<Vec<_>>();
+    let filename = args.get(1).ok_or("Invalid input")?;
+    let report = args.iter().any(|a| a == "--indices");
+    let window = args
+        .iter()
+        .position(|a| a == "--window")
+        .map(|idx| args.get(idx + 1).and_then(|k| k.parse::<usize>().ok()))
+        .map(|k| {
+            k.filter(|k| *k > 0)
+                .ok_or("Window must be a positive number")
+        })
+        .transpose()?;
+
+    let open = || File::open(filename).map(BufReader::new);
+
+    if let Some(k) = window {
+        println!("Window {}: {}", k, count(open()?, k, report)?);
+        return Ok(());
+    }
+
+    let (result_a, result_b) = if report {
+        (count(open()?, 1, report)?, count(open()?, 3, report)?)
+    } else {
+        solve(&std::fs::read_to_string(filename)?)?
+    };
+
+    println!("Task A: {}\nTask B: {}", result_a, result_b);
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const INPUT: &str = "199\n200\n208\n210\n200\n207\n240\n269\n260\n263";
+
+    #[test]
+    fn test_increases() {
+        let indices = |k| {
+            increases(INPUT.as_bytes(), k)
+                .collect::<Result<Vec<_>, _>>()
+                .unwrap()
+        };
+
+        assert_eq!(indices(1), vec![1, 2, 3, 5, 6, 7, 9]);
+        assert_eq!(indices(3), vec![3, 6, 7, 8, 9]);
+        assert_eq!(indices(10), vec![]);
+        assert_eq!(indices(usize::MAX), vec![]);
+    }
+}
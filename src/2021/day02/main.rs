@@ -0,0 +1,585 @@
+use lazy_static::lazy_static;
+use regex::Regex;
+use std::{error::Error, fmt};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Model {
+    Simple,
+    Advanced,
+    Spatial,
+}
+
+impl Model {
+    pub fn parse(input: &str) -> Option<Self> {
+        match input {
+            "simple" => Some(Self::Simple),
+            "advanced" => Some(Self::Advanced),
+            "3d" => Some(Self::Spatial),
+            _ => None,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Simple => "simple",
+            Self::Advanced => "advanced",
+            Self::Spatial => "3d",
+        }
+    }
+
+    pub fn build(&self) -> Box<dyn Boat> {
+        match self {
+            Self::Simple => Box::new(SimpleBoat::new()),
+            Self::Advanced => Box::new(AdvancedBoat::new()),
+            Self::Spatial => Box::new(SpatialBoat::new()),
+        }
+    }
+}
+
+impl fmt::Display for Model {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Simple => write!(f, "simple boat"),
+            Self::Advanced => write!(f, "advanced boat"),
+            Self::Spatial => write!(f, "3D boat"),
+        }
+    }
+}
+
+type Action<B> = Option<fn(&mut B, i32)>;
+
+// A command with the action every boat takes for it, boats without an
+// action reject the command.
+#[derive(Debug)]
+struct Spec {
+    name: &'static str,
+    value: bool,
+    valid: fn(i32) -> bool,
+    simple: Action<SimpleBoat>,
+    advanced: Action<AdvancedBoat>,
+    spatial: Action<SpatialBoat>,
+}
+
+// New commands are added here and nowhere else.
+const COMMANDS: &[Spec] = &[
+    Spec {
+        name: "forward",
+        value: true,
+        valid: |_| true,
+        simple: Some(|boat, value| boat.horizontal += value),
+        advanced: Some(|boat, value| {
+            boat.horizontal += value;
+            boat.depth += boat.aim * value
+        }),
+        spatial: Some(|boat, value| boat.advance(value)),
+    },
+    Spec {
+        name: "up",
+        value: true,
+        valid: |_| true,
+        simple: Some(|boat, value| boat.depth -= value),
+        advanced: Some(|boat, value| boat.aim -= value),
+        spatial: Some(|boat, value| boat.pitch -= value),
+    },
+    Spec {
+        name: "down",
+        value: true,
+        valid: |_| true,
+        simple: Some(|boat, value| boat.depth += value),
+        advanced: Some(|boat, value| boat.aim += value),
+        spatial: Some(|boat, value| boat.pitch += value),
+    },
+    Spec {
+        name: "back",
+        value: true,
+        valid: |_| true,
+        simple: Some(|boat, value| boat.horizontal -= value),
+        advanced: Some(|boat, value| {
+            boat.horizontal -= value;
+            boat.depth -= boat.aim * value
+        }),
+        spatial: Some(|boat, value| boat.advance(-value)),
+    },
+    Spec {
+        name: "turn",
+        value: true,
+        valid: |degrees| degrees % 90 == 0,
+        simple: None,
+        advanced: None,
+        spatial: Some(|boat, degrees| boat.heading = (boat.heading + degrees).rem_euclid(360)),
+    },
+    Spec {
+        name: "surface",
+        value: false,
+        valid: |_| true,
+        simple: Some(|boat, _| boat.depth = 0),
+        advanced: Some(|boat, _| {
+            boat.depth = 0;
+            boat.aim = 0;
+        }),
+        spatial: Some(|boat, _| {
+            boat.depth = 0;
+            boat.pitch = 0;
+        }),
+    },
+];
+
+#[derive(Debug, Clone, Copy)]
+struct Command {
+    spec: &'static Spec,
+    value: i32,
+}
+
+impl Command {
+    pub fn parse(input: &str) -> Result<Self, String> {
+        lazy_static! {
+            static ref RE: Regex = Regex::new(r"^([a-z]+)(?:\s+(\d+))?$").unwrap();
+        }
+
+        let caps = RE
+            .captures(input)
+            .ok_or_else(|| format!("Invalid line: {}", input))?;
+        let name = &caps[1];
+        let spec = COMMANDS
+            .iter()
+            .find(|spec| spec.name == name)
+            .ok_or_else(|| {
+                let names = COMMANDS.iter().map(|s| s.name).collect::<Vec<_>>();
+                format!("Unknown command {}, expected {}", name, names.join(", "))
+            })?;
+
+        let value = match (spec.value, caps.get(2)) {
+            (true, Some(value)) => value.as_str().parse::<i32>().ok(),
+            (false, None) => Some(0),
+            _ => None,
+        };
+
+        match value {
+            Some(value) if (spec.valid)(value) => Ok(Self { spec, value }),
+            _ => Err(format!("Invalid line: {}", input)),
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        self.spec.name
+    }
+
+    pub fn supports(&self, model: Model) -> bool {
+        match model {
+            Model::Simple => self.spec.simple.is_some(),
+            Model::Advanced => self.spec.advanced.is_some(),
+            Model::Spatial => self.spec.spatial.is_some(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Sample {
+    x: i32,
+    y: i32,
+    depth: i32,
+    aim: i32,
+}
+
+trait Boat {
+    fn model(&self) -> Model;
+    fn apply(&mut self, cmd: &Command);
+    fn result(&self) -> i32;
+    fn sample(&self) -> Sample;
+
+    fn perform(&mut self, cmd: &Command) -> Result<(), String> {
+        if cmd.supports(self.model()) {
+            self.apply(cmd);
+            Ok(())
+        } else {
+            Err(format!(
+                "{} is not supported by the {}",
+                cmd.name(),
+                self.model()
+            ))
+        }
+    }
+}
+
+struct SimpleBoat {
+    horizontal: i32,
+    depth: i32,
+}
+
+struct AdvancedBoat {
+    horizontal: i32,
+    depth: i32,
+    aim: i32,
+}
+
+// Moves in the horizontal plane along its heading, with the pitch working
+// like the aim of the advanced boat.
+struct SpatialBoat {
+    x: i32,
+    y: i32,
+    depth: i32,
+    heading: i32,
+    pitch: i32,
+}
+
+impl SimpleBoat {
+    pub fn new() -> Self {
+        Self {
+            horizontal: 0,
+            depth: 0,
+        }
+    }
+}
+
+impl AdvancedBoat {
+    pub fn new() -> Self {
+        Self {
+            horizontal: 0,
+            depth: 0,
+            aim: 0,
+        }
+    }
+}
+
+impl SpatialBoat {
+    pub fn new() -> Self {
+        Self {
+            x: 0,
+            y: 0,
+            depth: 0,
+            heading: 0,
+            pitch: 0,
+        }
+    }
+
+    fn advance(&mut self, value: i32) {
+        let (dx, dy) = match self.heading {
+            0 => (1, 0),
+            90 => (0, 1),
+            180 => (-1, 0),
+            _ => (0, -1),
+        };
+
+        self.x += dx * value;
+        self.y += dy * value;
+        self.depth += self.pitch * value;
+    }
+}
+
+// Commands a boat does not declare support for are rejected by `perform`
+// before they reach `apply`.
+impl Boat for SimpleBoat {
+    fn model(&self) -> Model {
+        Model::Simple
+    }
+
+    fn apply(&mut self, cmd: &Command) {
+        if let Some(action) = cmd.spec.simple {
+            action(self, cmd.value);
+        }
+    }
+
+    fn result(&self) -> i32 {
+        self.horizontal * self.depth
+    }
+
+    fn sample(&self) -> Sample {
+        Sample {
+            x: self.horizontal,
+            y: 0,
+            depth: self.depth,
+            aim: 0,
+        }
+    }
+}
+
+impl Boat for AdvancedBoat {
+    fn model(&self) -> Model {
+        Model::Advanced
+    }
+
+    fn apply(&mut self, cmd: &Command) {
+        if let Some(action) = cmd.spec.advanced {
+            action(self, cmd.value);
+        }
+    }
+
+    fn result(&self) -> i32 {
+        self.horizontal * self.depth
+    }
+
+    fn sample(&self) -> Sample {
+        Sample {
+            x: self.horizontal,
+            y: 0,
+            depth: self.depth,
+            aim: self.aim,
+        }
+    }
+}
+
+impl Boat for SpatialBoat {
+    fn model(&self) -> Model {
+        Model::Spatial
+    }
+
+    fn apply(&mut self, cmd: &Command) {
+        if let Some(action) = cmd.spec.spatial {
+            action(self, cmd.value);
+        }
+    }
+
+    fn result(&self) -> i32 {
+        (self.x.abs() + self.y.abs()) * self.depth
+    }
+
+    fn sample(&self) -> Sample {
+        Sample {
+            x: self.x,
+            y: self.y,
+            depth: self.depth,
+            aim: self.pitch,
+        }
+    }
+}
+
+// Wraps a boat and keeps its sample after every performed command.
+struct Recorder {
+    boat: Box<dyn Boat>,
+    track: Vec<Sample>,
+}
+
+impl Recorder {
+    pub fn new(boat: Box<dyn Boat>) -> Self {
+        Self {
+            boat,
+            track: vec![],
+        }
+    }
+
+    // Deepest point of the track and the 1-based step it was first reached at.
+    pub fn max_depth(&self) -> Option<(usize, i32)> {
+        self.track
+            .iter()
+            .enumerate()
+            .rev()
+            .max_by_key(|(_, sample)| sample.depth)
+            .map(|(idx, sample)| (idx + 1, sample.depth))
+    }
+
+    pub fn csv(&self) -> String {
+        let mut result = "step,x,y,depth,aim\n".to_owned();
+
+        for (idx, s) in self.track.iter().enumerate() {
+            result += &format!("{},{},{},{},{}\n", idx + 1, s.x, s.y, s.depth, s.aim);
+        }
+
+        result
+    }
+
+    // Side view of the track, the y axis only shows up in the CSV.
+    pub fn svg(&self) -> String {
+        let points = std::iter::once((0, 0))
+            .chain(self.track.iter().map(|s| (s.x, s.depth)))
+            .collect::<Vec<_>>();
+
+        let (xmin, xmax, ymin, ymax) =
+            points
+                .iter()
+                .fold((0, 0, 0, 0), |(xmin, xmax, ymin, ymax), (x, y)| {
+                    (xmin.min(*x), xmax.max(*x), ymin.min(*y), ymax.max(*y))
+                });
+
+        let polyline = points
+            .iter()
+            .map(|(x, y)| format!("{},{}", x, y))
+            .collect::<Vec<_>>()
+            .join(" ");
+
+        format!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" viewBox=\"{} {} {} {}\">\n  \
+             <polyline fill=\"none\" stroke=\"black\" vector-effect=\"non-scaling-stroke\" points=\"{}\"/>\n\
+             </svg>\n",
+            xmin,
+            ymin,
+            (xmax - xmin).max(1),
+            (ymax - ymin).max(1),
+            polyline
+        )
+    }
+}
+
+impl Boat for Recorder {
+    fn model(&self) -> Model {
+        self.boat.model()
+    }
+
+    fn apply(&mut self, cmd: &Command) {
+        self.boat.apply(cmd);
+        self.track.push(self.boat.sample());
+    }
+
+    fn result(&self) -> i32 {
+        self.boat.result()
+    }
+
+    fn sample(&self) -> Sample {
+        self.boat.sample()
+    }
+}
+
+fn navigate(boat: &mut dyn Boat, commands: &[Command]) -> Result<i32, String> {
+    for (idx, command) in commands.iter().enumerate() {
+        boat.perform(command)
+            .map_err(|e| format!("line {}: {}", idx + 1, e))?;
+    }
+
+    Ok(boat.result())
+}
+
+fn run(model: Model, commands: &[Command], track: Option<&str>) -> Result<i32, Box<dyn Error>> {
+    let prefix = match track {
+        Some(prefix) => prefix,
+        None => return Ok(navigate(model.build().as_mut(), commands)?),
+    };
+
+    let mut recorder = Recorder::new(model.build());
+    let result = navigate(&mut recorder, commands)?;
+
+    let name = format!("{}-{}", prefix, model.name());
+    std::fs::write(format!("{}.csv", name), recorder.csv())?;
+    std::fs::write(format!("{}.svg", name), recorder.svg())?;
+
+    match recorder.max_depth() {
+        Some((step, depth)) => println!("{}: max depth {} at step {}", model, depth, step),
+        None => println!("{}: no commands", model),
+    }
+
+    Ok(result)
+}
+
+fn parse(content: &str) -> Result<Vec<Command>, Box<dyn Error>> {
+    let lines = content
+        .lines()
+        .map(Command::parse)
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(lines)
+}
+
+pub(crate) fn solve(content: &str) -> Result<(i32, i32), Box<dyn Error>> {
+    let lines = parse(content)?;
+
+    Ok((
+        run(Model::Simple, &lines, None)?,
+        run(Model::Advanced, &lines, None)?,
+    ))
+}
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let args = std::env::args().collect::<Vec<_>>();
+    let input = args.get(1).ok_or("Invalid input")?;
+    let content = std::fs::read_to_string(input)?;
+    let lines = parse(&content)?;
+
+    let track = args
+        .iter()
+        .position(|a| a == "--track")
+        .map(|idx| {
+            args.get(idx + 1)
+                .map(String::as_str)
+                .ok_or("Missing track prefix")
+        })
+        .transpose()?;
+
+    if let Some(idx) = args.iter().position(|a| a == "--boat") {
+        let model = args
+            .get(idx + 1)
+            .and_then(|m| Model::parse(m))
+            .ok_or("Unknown boat, expected simple, advanced or 3d")?;
+
+        println!("{}: {}", model, run(model, &lines, track)?);
+        return Ok(());
+    }
+
+    let (result_a, result_b) = match track {
+        Some(_) => (
+            run(Model::Simple, &lines, track)?,
+            run(Model::Advanced, &lines, track)?,
+        ),
+        None => solve(&content)?,
+    };
+
+    println!("Task A: {}\nTask B: {}", result_a, result_b);
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_registry() {
+        let commands = ["forward 5", "down 2", "turn 90", "forward 3", "back 1"]
+            .iter()
+            .map(|line| Command::parse(line))
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+
+        assert_eq!(navigate(&mut SpatialBoat::new(), &commands), Ok(7 * 4));
+        assert_eq!(
+            navigate(&mut SimpleBoat::new(), &commands),
+            Err("line 3: turn is not supported by the simple boat".to_owned())
+        );
+
+        assert!(Command::parse("turn 45").is_err());
+        assert!(Command::parse("surface 1").is_err());
+        assert!(Command::parse("forward -3").is_err());
+        assert_eq!(
+            Command::parse("sideways 1").err(),
+            Some(
+                "Unknown command sideways, expected forward, up, down, back, turn, surface"
+                    .to_owned()
+            )
+        );
+    }
+
+    #[test]
+    fn test_recorder() {
+        let commands = [
+            "forward 5",
+            "down 5",
+            "forward 8",
+            "up 3",
+            "down 8",
+            "forward 2",
+        ]
+        .iter()
+        .map(|line| Command::parse(line))
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+
+        let mut recorder = Recorder::new(Model::Advanced.build());
+        assert_eq!(navigate(&mut recorder, &commands), Ok(900));
+        assert_eq!(recorder.max_depth(), Some((6, 60)));
+        assert!(recorder.csv().ends_with("5,13,0,40,10\n6,15,0,60,10\n"));
+        assert!(recorder
+            .svg()
+            .contains("points=\"0,0 5,0 5,0 13,40 13,40 13,40 15,60\""));
+
+        let mut recorder = Recorder::new(Model::Simple.build());
+        assert_eq!(navigate(&mut recorder, &commands), Ok(150));
+        assert_eq!(recorder.max_depth(), Some((5, 10)));
+
+        let commands = ["down 1", "turn 180", "forward 2", "turn 90", "forward 3"]
+            .iter()
+            .map(|line| Command::parse(line))
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+
+        let mut recorder = Recorder::new(Model::Spatial.build());
+        assert_eq!(navigate(&mut recorder, &commands), Ok(5 * 5));
+        assert!(recorder.csv().ends_with("5,-2,-3,5,1\n"));
+    }
+}
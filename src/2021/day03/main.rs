@@ -0,0 +1,525 @@
+use advent2021::bigint::BigUint;
+use std::convert::TryFrom;
+use std::error::Error;
+use std::fmt;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Bit {
+    Zero,
+    One,
+}
+
+impl Bit {
+    pub fn one(&self) -> bool {
+        *self == Bit::One
+    }
+
+    pub fn to_char(self) -> char {
+        match self {
+            Self::Zero => '0',
+            Self::One => '1',
+        }
+    }
+}
+
+impl TryFrom<char> for Bit {
+    type Error = &'static str;
+
+    fn try_from(value: char) -> Result<Self, Self::Error> {
+        match value {
+            '0' => Ok(Bit::Zero),
+            '1' => Ok(Bit::One),
+            _ => Err("Invalid input"),
+        }
+    }
+}
+
+const WORD: usize = 64;
+
+// Bit `i` of a row, counting from the left, is bit `i % 64` of word `i / 64`.
+#[derive(PartialEq)]
+struct BitRow {
+    size: usize,
+    words: Vec<u64>,
+}
+
+impl BitRow {
+    pub fn parse(input: &str) -> Result<Self, Box<dyn Error>> {
+        let mut row = Self::zeros(input.len());
+
+        for (i, c) in input.chars().enumerate() {
+            if Bit::try_from(c)?.one() {
+                row.set(i);
+            }
+        }
+
+        Ok(row)
+    }
+
+    pub fn zeros(size: usize) -> Self {
+        let words = vec![0; size.div_ceil(WORD)];
+        Self { size, words }
+    }
+
+    pub fn set(&mut self, position: usize) {
+        self.words[position / WORD] |= 1 << (position % WORD);
+    }
+
+    pub fn value(&self) -> BigUint {
+        BigUint::from_bits(self.bits().map(|bit| bit.one()))
+    }
+
+    pub fn bits(&self) -> impl DoubleEndedIterator<Item = Bit> + '_ {
+        (0..self.size).map(move |i| self.at(i))
+    }
+
+    pub fn at(&self, position: usize) -> Bit {
+        if (self.words[position / WORD] >> (position % WORD)) & 1 == 1 {
+            Bit::One
+        } else {
+            Bit::Zero
+        }
+    }
+}
+
+impl fmt::Debug for BitRow {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let bits = self.bits().map(|b| b.to_char()).collect::<String>();
+        f.write_str(&format!(
+            "BitRow {{ size: {}, bits: {} }}",
+            self.size, &bits
+        ))
+    }
+}
+
+#[derive(Debug, PartialEq)]
+enum Common {
+    Empty,
+    Filled {
+        size: usize,
+        count: Vec<usize>,
+        total: usize,
+    },
+}
+
+impl Common {
+    fn new() -> Self {
+        Common::Empty
+    }
+
+    // Visits only the set bits of every word, so the work per row follows
+    // its popcount rather than its width.
+    fn add(self, row: &BitRow) -> Result<Self, Box<dyn Error>> {
+        match self {
+            Self::Empty => {
+                let size = row.size;
+                let count = vec![0; size];
+                let total = 0;
+
+                Common::Filled { size, count, total }.add(row)
+            }
+
+            Self::Filled {
+                size,
+                mut count,
+                total,
+            } => {
+                if size != row.size {
+                    return Err("Invalid input size".into());
+                }
+
+                for (w, word) in row.words.iter().enumerate() {
+                    let mut word = *word;
+                    while word != 0 {
+                        count[w * WORD + word.trailing_zeros() as usize] += 1;
+                        word &= word - 1;
+                    }
+                }
+
+                let total = total + 1;
+
+                Ok(Common::Filled { size, count, total })
+            }
+        }
+    }
+
+    fn most_common(&self) -> Result<BitRow, Box<dyn Error>> {
+        match self {
+            Self::Empty => Err("Empty common".into()),
+            Self::Filled { size, total, count } => {
+                let mut row = BitRow::zeros(*size);
+                for (position, i) in count.iter().enumerate() {
+                    if i * 2 >= *total {
+                        row.set(position);
+                    }
+                }
+
+                Ok(row)
+            }
+        }
+    }
+
+    fn least_common(&self) -> Result<BitRow, Box<dyn Error>> {
+        match self {
+            Self::Empty => Err("Empty common".into()),
+            Self::Filled { size, total, count } => {
+                let mut row = BitRow::zeros(*size);
+                for (position, i) in count.iter().enumerate() {
+                    if i * 2 < *total {
+                        row.set(position);
+                    }
+                }
+
+                Ok(row)
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Criterion {
+    MostCommon,
+    LeastCommon,
+}
+
+impl Criterion {
+    pub fn parse(input: &str) -> Option<Self> {
+        match input {
+            "most" => Some(Self::MostCommon),
+            "least" => Some(Self::LeastCommon),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Tie {
+    One,
+    Zero,
+    KeepAll,
+}
+
+impl Tie {
+    pub fn parse(input: &str) -> Option<Self> {
+        match input {
+            "1" => Some(Self::One),
+            "0" => Some(Self::Zero),
+            "keep" => Some(Self::KeepAll),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+enum RatingError {
+    NoRows,
+    Exhausted { position: usize },
+    TooLong { remaining: usize },
+}
+
+impl fmt::Display for RatingError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NoRows => write!(f, "no rows to search"),
+            Self::Exhausted { position } => write!(f, "no rows left at bit {}", position),
+            Self::TooLong { remaining } => {
+                write!(f, "{} rows left after the last bit", remaining)
+            }
+        }
+    }
+}
+
+impl Error for RatingError {}
+
+// One bit position of the search: the bit rows had to match (None when the
+// tie kept every row) and how many rows survived.
+#[derive(Debug, PartialEq)]
+struct Step {
+    position: usize,
+    kept: Option<Bit>,
+    survivors: usize,
+}
+
+impl fmt::Display for Step {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let kept = self.kept.map(|b| b.to_char()).unwrap_or('*');
+        write!(
+            f,
+            "bit {:3} keep {} -> {} rows",
+            self.position, kept, self.survivors
+        )
+    }
+}
+
+#[derive(Debug)]
+struct Rating<'a> {
+    row: &'a BitRow,
+    path: Vec<Step>,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct RatingQuery {
+    criterion: Criterion,
+    tie: Tie,
+}
+
+impl RatingQuery {
+    pub fn new(criterion: Criterion, tie: Tie) -> Self {
+        Self { criterion, tie }
+    }
+
+    pub fn oxygen() -> Self {
+        Self::new(Criterion::MostCommon, Tie::One)
+    }
+
+    pub fn co2() -> Self {
+        Self::new(Criterion::LeastCommon, Tie::Zero)
+    }
+
+    fn select(&self, ones: usize, zeros: usize) -> Option<Bit> {
+        let (more, less) = if ones > zeros {
+            (Bit::One, Bit::Zero)
+        } else if zeros > ones {
+            (Bit::Zero, Bit::One)
+        } else {
+            return match self.tie {
+                Tie::One => Some(Bit::One),
+                Tie::Zero => Some(Bit::Zero),
+                Tie::KeepAll => None,
+            };
+        };
+
+        match self.criterion {
+            Criterion::MostCommon => Some(more),
+            Criterion::LeastCommon => Some(less),
+        }
+    }
+
+    pub fn run<'a>(&self, rows: &[&'a BitRow]) -> Result<Rating<'a>, RatingError> {
+        let mut rows = rows.to_vec();
+        let mut path = vec![];
+        let size = rows.first().ok_or(RatingError::NoRows)?.size;
+
+        for position in 0..size {
+            if rows.len() == 1 {
+                break;
+            }
+
+            let ones = rows.iter().filter(|row| row.at(position).one()).count();
+            let kept = self.select(ones, rows.len() - ones);
+
+            if let Some(bit) = kept {
+                rows.retain(|row| row.at(position) == bit);
+            }
+
+            path.push(Step {
+                position,
+                kept,
+                survivors: rows.len(),
+            });
+
+            if rows.is_empty() {
+                return Err(RatingError::Exhausted { position });
+            }
+        }
+
+        match *rows.as_slice() {
+            [row] => Ok(Rating { row, path }),
+            _ => Err(RatingError::TooLong {
+                remaining: rows.len(),
+            }),
+        }
+    }
+}
+
+fn parse(raw: &str) -> Result<Vec<BitRow>, Box<dyn Error>> {
+    raw.lines().map(BitRow::parse).collect()
+}
+
+pub(crate) fn solve(raw: &str) -> Result<(BigUint, BigUint), Box<dyn Error>> {
+    let content = parse(raw)?;
+
+    let common = content
+        .iter()
+        .try_fold(Common::new(), |acc, e| acc.add(e))?;
+
+    let gamma = common.most_common()?.value();
+    let epsilon = common.least_common()?.value();
+
+    let all: Vec<_> = content.iter().collect();
+    let oxy = RatingQuery::oxygen().run(&all)?;
+    let co2 = RatingQuery::co2().run(&all)?;
+
+    let result_a = &gamma * &epsilon;
+    let result_b = &oxy.row.value() * &co2.row.value();
+
+    Ok((result_a, result_b))
+}
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let input = std::env::args().nth(1).ok_or("Invalid input")?;
+    let raw = std::fs::read_to_string(input)?;
+    let content = parse(&raw)?;
+
+    let all: Vec<_> = content.iter().collect();
+    let args = std::env::args().collect::<Vec<_>>();
+
+    if let Some(idx) = args.iter().position(|a| a == "--query") {
+        let criterion = args.get(idx + 1).and_then(|c| Criterion::parse(c));
+        let tie = args.get(idx + 2).and_then(|t| Tie::parse(t));
+        let query = match (criterion, tie) {
+            (Some(criterion), Some(tie)) => RatingQuery::new(criterion, tie),
+            _ => return Err("Expected --query <most|least> <1|0|keep>".into()),
+        };
+
+        let rating = query.run(&all)?;
+        for step in rating.path.iter() {
+            println!("{}", step);
+        }
+        println!("Rating: {}", rating.row.value());
+        return Ok(());
+    }
+
+    if args.iter().any(|a| a == "--path") {
+        let oxy = RatingQuery::oxygen().run(&all)?;
+        let co2 = RatingQuery::co2().run(&all)?;
+
+        for (name, rating) in [("oxygen", &oxy), ("co2", &co2)] {
+            for step in rating.path.iter() {
+                println!("{}: {}", name, step);
+            }
+        }
+    }
+
+    let (result_a, result_b) = solve(&raw)?;
+    println!("Task A: {}\nTask B: {}", result_a, result_b);
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse_bitrow() {
+        let row = BitRow::parse("1011").unwrap();
+        let expected = BitRow {
+            size: 4,
+            words: vec![0b1101],
+        };
+        assert_eq!(row, expected);
+        assert_eq!(
+            row.bits().collect::<Vec<_>>(),
+            vec![Bit::One, Bit::Zero, Bit::One, Bit::One]
+        );
+    }
+
+    #[test]
+    fn test_add() {
+        let common = Common::new();
+        let common = common.add(&BitRow::parse("1100").unwrap()).unwrap();
+        let common = common.add(&BitRow::parse("0100").unwrap()).unwrap();
+
+        let expected = Common::Filled {
+            size: 4,
+            total: 2,
+            count: vec![1, 2, 0, 0],
+        };
+
+        assert_eq!(common, expected);
+    }
+
+    #[test]
+    fn test_most_common() {
+        let common = Common::Filled {
+            size: 4,
+            total: 5,
+            count: vec![4, 3, 2, 1],
+        };
+
+        let expected = BitRow::parse("1100").unwrap();
+        assert_eq!(common.most_common().unwrap(), expected);
+    }
+
+    #[test]
+    fn test_least_common() {
+        let common = Common::Filled {
+            size: 5,
+            total: 6,
+            count: vec![4, 3, 2, 1, 6],
+        };
+
+        let expected = BitRow::parse("00110").unwrap();
+        assert_eq!(common.least_common().unwrap(), expected);
+    }
+
+    #[test]
+    fn test_value() {
+        let row = BitRow::parse("01100").unwrap();
+        assert_eq!(row.value(), BigUint::from(12));
+
+        let wide = format!("{}1{}", "0".repeat(100), "0".repeat(99));
+        let row = BitRow::parse(&wide).unwrap();
+        assert_eq!(row.words.len(), 4);
+        assert_eq!(row.value().to_string(), (1u128 << 99).to_string());
+
+        let row = BitRow::parse(&"1".repeat(129)).unwrap();
+        assert_eq!(
+            row.value().to_string(),
+            "680564733841876926926749214863536422911"
+        );
+    }
+
+    #[test]
+    fn test_rating_query() {
+        let rows = [
+            "00100", "11110", "10110", "10111", "10101", "01111", "00111",
+        ]
+        .iter()
+        .map(|r| BitRow::parse(r).unwrap())
+        .collect::<Vec<_>>();
+        let all = rows.iter().collect::<Vec<_>>();
+
+        let oxygen = RatingQuery::oxygen().run(&all).unwrap();
+        assert_eq!(oxygen.row.value(), BigUint::from(0b10111));
+        let survivors = oxygen.path.iter().map(|s| s.survivors).collect::<Vec<_>>();
+        assert_eq!(survivors, vec![4, 3, 3, 2, 1]);
+
+        let co2 = RatingQuery::co2().run(&all).unwrap();
+        assert_eq!(co2.row.value(), BigUint::from(0b01111));
+
+        let twins = [&rows[0], &rows[0]];
+        let keep = RatingQuery::new(Criterion::MostCommon, Tie::KeepAll);
+        assert_eq!(
+            keep.run(&twins).unwrap_err(),
+            RatingError::TooLong { remaining: 2 }
+        );
+
+        let same = [&rows[1], &rows[2]];
+        assert_eq!(
+            RatingQuery::co2().run(&same).unwrap_err(),
+            RatingError::Exhausted { position: 0 }
+        );
+        assert_eq!(
+            RatingQuery::co2().run(&[]).unwrap_err(),
+            RatingError::NoRows
+        );
+    }
+
+    #[test]
+    fn test_add_wide() {
+        let mut row = BitRow::zeros(3000);
+        row.set(0);
+        row.set(2999);
+
+        let common = Common::new().add(&row).unwrap().add(&row).unwrap();
+        match common {
+            Common::Filled { count, total, .. } => {
+                assert_eq!(total, 2);
+                assert_eq!((count[0], count[1], count[2999]), (2, 0, 2));
+            }
+            Common::Empty => panic!("Empty common"),
+        }
+    }
+}
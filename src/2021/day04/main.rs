@@ -0,0 +1,453 @@
+use advent2021::check::{self, Violation};
+use std::collections::{HashMap, VecDeque};
+use std::{error::Error, fmt};
+
+#[derive(Debug, Default, Clone, Copy)]
+struct Rules {
+    diagonals: bool,
+    full_house: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Line {
+    Row(usize),
+    Col(usize),
+    Diagonal,
+    AntiDiagonal,
+    FullHouse,
+}
+
+impl fmt::Display for Line {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Row(row) => write!(f, "row {}", row),
+            Self::Col(col) => write!(f, "col {}", col),
+            Self::Diagonal => write!(f, "diagonal"),
+            Self::AntiDiagonal => write!(f, "anti-diagonal"),
+            Self::FullHouse => write!(f, "full house"),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+struct Win {
+    card: usize,
+    turn: usize,
+    number: i32,
+    unmarked: i32,
+    line: Line,
+}
+
+impl Win {
+    fn score(&self) -> i32 {
+        self.number * self.unmarked
+    }
+}
+
+// Wins in the order they happened, and the cards which never completed.
+#[derive(Debug)]
+struct Timeline {
+    wins: Vec<Win>,
+    pending: Vec<usize>,
+}
+
+impl fmt::Display for Timeline {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "card  turn  number  unmarked   score  line")?;
+
+        for win in &self.wins {
+            writeln!(
+                f,
+                "{:4}  {:4}  {:6}  {:8}  {:6}  {}",
+                win.card,
+                win.turn,
+                win.number,
+                win.unmarked,
+                win.score(),
+                win.line
+            )?;
+        }
+
+        let pending = self
+            .pending
+            .iter()
+            .map(|c| c.to_string())
+            .collect::<Vec<_>>();
+
+        match pending.len() {
+            0 => writeln!(f, "every card completed"),
+            _ => writeln!(f, "never completed: {}", pending.join(", ")),
+        }
+    }
+}
+
+struct Game {
+    numbers: VecDeque<i32>,
+    cards: Vec<(Card, bool)>,
+    positions: HashMap<i32, Vec<(usize, usize)>>,
+    rules: Rules,
+    turn: usize,
+    winners: VecDeque<Win>,
+}
+
+impl Game {
+    fn parse(input: &str, rules: Rules) -> Result<Self, Box<dyn Error>> {
+        let mut parts = input.split("\n\n");
+
+        let numbers_raw = parts.next().ok_or("invalid input")?;
+        let numbers = numbers_raw
+            .trim()
+            .split(',')
+            .map(|p| p.parse::<i32>())
+            .collect::<Result<VecDeque<_>, _>>()?;
+
+        let cards = parts
+            .filter(|p| !p.trim().is_empty())
+            .map(Card::parse)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        if let Some(card) = cards.iter().find(|c| c.size != cards[0].size) {
+            let message = format!("mixed board sizes {} and {}", cards[0].size, card.size);
+            return Err(message.into());
+        }
+
+        let mut positions: HashMap<i32, Vec<(usize, usize)>> = HashMap::new();
+        for (card_idx, card) in cards.iter().enumerate() {
+            for (idx, (n, _)) in card.numbers.iter().enumerate() {
+                positions.entry(*n).or_default().push((card_idx, idx));
+            }
+        }
+
+        let cards = cards.into_iter().map(|c| (c, false)).collect();
+
+        Ok(Self {
+            numbers,
+            cards,
+            positions,
+            rules,
+            turn: 0,
+            winners: VecDeque::new(),
+        })
+    }
+
+    // Only the cells holding the drawn number are touched, and every card
+    // keeps per-line counters, so a draw costs O(occurrences).
+    fn draw(&mut self, k: i32) {
+        self.turn += 1;
+
+        let positions = match self.positions.get(&k) {
+            Some(positions) => positions,
+            None => return,
+        };
+
+        for (card, idx) in positions {
+            self.cards[*card].0.mark(*idx);
+        }
+
+        for (card_idx, idx) in positions {
+            let (card, used) = &mut self.cards[*card_idx];
+            if *used {
+                continue;
+            }
+
+            if let Some(line) = card.completed(*idx, &self.rules) {
+                *used = true;
+                self.winners.push_back(Win {
+                    card: *card_idx,
+                    turn: self.turn,
+                    number: k,
+                    unmarked: card.sum_unmarked(),
+                    line,
+                });
+            }
+        }
+    }
+
+    fn next_win(&mut self) -> Option<Win> {
+        loop {
+            if let Some(win) = self.winners.pop_front() {
+                return Some(win);
+            }
+
+            let k = self.numbers.pop_front()?;
+            self.draw(k);
+        }
+    }
+
+    // Plays every remaining number.
+    fn timeline(mut self) -> Timeline {
+        let wins = std::iter::from_fn(|| self.next_win()).collect();
+        let pending = self
+            .cards
+            .iter()
+            .enumerate()
+            .filter(|(_, (_, used))| !used)
+            .map(|(idx, _)| idx)
+            .collect();
+
+        Timeline { wins, pending }
+    }
+}
+
+impl Iterator for Game {
+    type Item = i32;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_win().map(|win| win.score())
+    }
+}
+
+#[derive(Debug)]
+struct Card {
+    size: usize,
+    numbers: Vec<(i32, bool)>,
+    rows: Vec<usize>,
+    cols: Vec<usize>,
+    diagonals: [usize; 2],
+    marked: usize,
+}
+
+impl Card {
+    fn parse(input: &str) -> Result<Self, Box<dyn Error>> {
+        let rows = input
+            .lines()
+            .filter(|l| !l.trim().is_empty())
+            .map(|l| {
+                l.split_whitespace()
+                    .map(|s| s.parse::<i32>())
+                    .collect::<Result<Vec<_>, _>>()
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let size = rows.len();
+        if let Some(row) = rows.iter().find(|r| r.len() != size) {
+            let message = format!("board has {} rows but a row of {} numbers", size, row.len());
+            return Err(message.into());
+        }
+
+        let numbers = rows.into_iter().flatten().map(|n| (n, false)).collect();
+
+        Ok(Self {
+            size,
+            numbers,
+            rows: vec![0; size],
+            cols: vec![0; size],
+            diagonals: [0; 2],
+            marked: 0,
+        })
+    }
+
+    fn mark(&mut self, idx: usize) {
+        let (_, checked) = &mut self.numbers[idx];
+        if *checked {
+            return;
+        }
+
+        *checked = true;
+        let (row, col) = (idx / self.size, idx % self.size);
+
+        self.rows[row] += 1;
+        self.cols[col] += 1;
+        self.marked += 1;
+
+        if row == col {
+            self.diagonals[0] += 1;
+        }
+        if row + col + 1 == self.size {
+            self.diagonals[1] += 1;
+        }
+    }
+
+    fn sum_unmarked(&self) -> i32 {
+        self.numbers
+            .iter()
+            .filter(|(_, checked)| !checked)
+            .map(|(n, _)| n)
+            .sum()
+    }
+
+    // Only lines through the last marked cell can have been completed by it.
+    fn completed(&self, idx: usize, rules: &Rules) -> Option<Line> {
+        if rules.full_house {
+            return Some(Line::FullHouse).filter(|_| self.marked == self.numbers.len());
+        }
+
+        let (row, col) = (idx / self.size, idx % self.size);
+
+        if self.rows[row] == self.size {
+            Some(Line::Row(row))
+        } else if self.cols[col] == self.size {
+            Some(Line::Col(col))
+        } else if rules.diagonals && row == col && self.diagonals[0] == self.size {
+            Some(Line::Diagonal)
+        } else if rules.diagonals && row + col + 1 == self.size && self.diagonals[1] == self.size {
+            Some(Line::AntiDiagonal)
+        } else {
+            None
+        }
+    }
+}
+
+fn check_numbers(line: usize, input: &str) -> Vec<Violation> {
+    input
+        .split_whitespace()
+        .filter(|n| n.parse::<i32>().is_err())
+        .map(|n| Violation::new(line, format!("invalid number {:?}", n)))
+        .collect()
+}
+
+// Boards are square, and all of them take the size of the first one.
+fn check_board(board: &[(usize, &str)], expected: usize) -> Vec<Violation> {
+    let mut violations = vec![];
+    let (start, _) = board[0];
+
+    if board.len() != expected {
+        let message = format!("board has {} rows, expected {}", board.len(), expected);
+        violations.push(Violation::new(start, message));
+    }
+
+    for (line, row) in board {
+        let size = row.split_whitespace().count();
+        if size != expected {
+            let message = format!("row has {} numbers, expected {}", size, expected);
+            violations.push(Violation::new(*line, message));
+        }
+
+        violations.append(&mut check_numbers(*line, row));
+    }
+
+    violations
+}
+
+fn check(input: &str) -> Vec<Violation> {
+    let mut lines = input.lines().enumerate().map(|(idx, l)| (idx + 1, l));
+    let mut violations = match lines.next() {
+        Some((line, numbers)) => check_numbers(line, &numbers.replace(',', " ")),
+        None => return vec![Violation::new(1, "empty input")],
+    };
+
+    let mut boards = vec![];
+    let mut board = vec![];
+
+    for (line, row) in lines {
+        if row.trim().is_empty() {
+            if !board.is_empty() {
+                boards.push(board);
+                board = vec![];
+            }
+        } else {
+            board.push((line, row));
+        }
+    }
+
+    if !board.is_empty() {
+        boards.push(board);
+    }
+
+    if boards.is_empty() {
+        violations.push(Violation::new(1, "no boards found"));
+    }
+
+    let expected = boards.first().map(|b| b.len()).unwrap_or(0);
+    for board in boards {
+        violations.append(&mut check_board(&board, expected));
+    }
+
+    violations
+}
+
+fn scores(timeline: &Timeline) -> Result<(i32, i32), Box<dyn Error>> {
+    let result_a = timeline.wins.first().ok_or("Result A not found!")?.score();
+    let result_b = timeline.wins.last().ok_or("Result B not found!")?.score();
+
+    Ok((result_a, result_b))
+}
+
+pub(crate) fn solve(input: &str) -> Result<(i32, i32), Box<dyn Error>> {
+    scores(&Game::parse(input, Rules::default())?.timeline())
+}
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let filename = std::env::args().nth(1).ok_or("Invalid input")?;
+    let input = std::fs::read_to_string(filename)?;
+
+    if std::env::args().any(|a| a == "--check") {
+        return check::report(&check(&input));
+    }
+
+    let rules = Rules {
+        diagonals: std::env::args().any(|a| a == "--diagonals"),
+        full_house: std::env::args().any(|a| a == "--full-house"),
+    };
+    let report = std::env::args().any(|a| a == "--timeline");
+
+    let (result_a, result_b) = if rules.diagonals || rules.full_house || report {
+        let timeline = Game::parse(&input, rules)?.timeline();
+
+        if report {
+            print!("{}", timeline);
+        }
+
+        scores(&timeline)?
+    } else {
+        solve(&input)?
+    };
+
+    println!("Task A: {}\nTask B: {}", result_a, result_b);
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const INPUT: &str = "5,1,9,3,7,2\n\n1 2 3\n4 5 6\n7 8 9\n\n9 8 7\n6 5 4\n3 2 1";
+
+    #[test]
+    fn test_rules() {
+        let game = Game::parse(INPUT, Rules::default()).unwrap();
+        assert_eq!(game.collect::<Vec<_>>(), vec![2 * 18, 2 * 18]);
+
+        let diagonals = Rules {
+            diagonals: true,
+            ..Rules::default()
+        };
+        let game = Game::parse(INPUT, diagonals).unwrap();
+        assert_eq!(game.collect::<Vec<_>>(), vec![9 * 30, 9 * 30]);
+
+        let full_house = Rules {
+            full_house: true,
+            ..Rules::default()
+        };
+        let mut game = Game::parse(INPUT, full_house).unwrap();
+        assert_eq!(game.next(), None);
+    }
+
+    #[test]
+    fn test_timeline() {
+        let input = "5,1,9,3\n\n1 2 3\n4 5 6\n7 8 9\n\n2 9 1\n3 4 6\n8 7 5";
+        let rules = Rules {
+            diagonals: true,
+            ..Rules::default()
+        };
+        let timeline = Game::parse(input, rules).unwrap().timeline();
+
+        let expected = Win {
+            card: 0,
+            turn: 3,
+            number: 9,
+            unmarked: 30,
+            line: Line::Diagonal,
+        };
+        assert_eq!(timeline.wins, vec![expected]);
+        assert_eq!(timeline.pending, vec![1]);
+        assert!(timeline.to_string().ends_with("never completed: 1\n"));
+    }
+
+    #[test]
+    fn test_invalid_boards() {
+        assert!(Game::parse("1,2\n\n1 2\n3", Rules::default()).is_err());
+        assert!(Game::parse("1,2\n\n1 2\n3 4\n\n1", Rules::default()).is_err());
+    }
+}
@@ -0,0 +1,616 @@
+use advent2021::check::{self, Violation};
+use lazy_static::lazy_static;
+use regex::Regex;
+use std::collections::{HashMap, HashSet};
+use std::error::Error;
+
+// Bounding boxes up to this many cells are counted in a flat array.
+const DENSE_LIMIT: i64 = 1 << 22;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Threshold {
+    AtLeast(usize),
+    Exactly(usize),
+}
+
+impl Threshold {
+    fn matches(&self, count: usize) -> bool {
+        match *self {
+            Self::AtLeast(k) => count >= k,
+            Self::Exactly(k) => count == k,
+        }
+    }
+}
+
+enum Grid {
+    Sparse(HashMap<(i32, i32), usize>),
+    Dense {
+        xmin: i32,
+        ymin: i32,
+        width: usize,
+        cells: Vec<u32>,
+    },
+    Sweep(Vec<Line>),
+}
+
+struct Game {
+    grid: Grid,
+    threshold: Threshold,
+}
+
+impl Game {
+    pub fn new() -> Self {
+        Self::with_grid(Grid::Sparse(HashMap::new()))
+    }
+
+    pub fn dense(xmin: i32, ymin: i32, xmax: i32, ymax: i32) -> Self {
+        let width = (xmax as i64 - xmin as i64 + 1) as usize;
+        let height = (ymax as i64 - ymin as i64 + 1) as usize;
+        let cells = vec![0; width * height];
+
+        Self::with_grid(Grid::Dense {
+            xmin,
+            ymin,
+            width,
+            cells,
+        })
+    }
+
+    pub fn sweep() -> Self {
+        Self::with_grid(Grid::Sweep(vec![]))
+    }
+
+    fn with_grid(grid: Grid) -> Self {
+        let threshold = Threshold::AtLeast(2);
+        Self { grid, threshold }
+    }
+
+    // Dense grid for small bounding boxes, sweep line for everything else.
+    pub fn for_lines(lines: &[Line]) -> Self {
+        let bbox = lines
+            .iter()
+            .flat_map(|l| [l.start(), l.end()])
+            .fold(None, |bbox, (x, y)| match bbox {
+                None => Some((x, y, x, y)),
+                Some((x0, y0, x1, y1)) => Some((x0.min(x), y0.min(y), x1.max(x), y1.max(y))),
+            });
+
+        match bbox {
+            Some((x0, y0, x1, y1))
+                if (x1 as i64 - x0 as i64 + 1) * (y1 as i64 - y0 as i64 + 1) <= DENSE_LIMIT =>
+            {
+                Self::dense(x0, y0, x1, y1)
+            }
+            _ => Self::sweep(),
+        }
+    }
+
+    pub fn with_threshold(self, threshold: Threshold) -> Self {
+        Self { threshold, ..self }
+    }
+
+    fn add_line(&mut self, line: &Line) {
+        if let Grid::Sweep(lines) = &mut self.grid {
+            lines.push(line.clone());
+            return;
+        }
+
+        let (x0, y0) = line.start();
+        let (dx, dy) = line.step();
+
+        for n in 0..line.size() {
+            let (x, y) = (x0 as i64 + n * dx, y0 as i64 + n * dy);
+            self.add_point(x as i32, y as i32);
+        }
+    }
+
+    fn result(&self) -> usize {
+        match &self.grid {
+            Grid::Sparse(grid) => grid
+                .values()
+                .filter(|v| self.threshold.matches(**v))
+                .count(),
+            Grid::Dense { cells, .. } => cells
+                .iter()
+                .filter(|v| **v > 0 && self.threshold.matches(**v as usize))
+                .count(),
+            Grid::Sweep(lines) => sweep(lines, self.threshold),
+        }
+    }
+
+    fn add_point(&mut self, x: i32, y: i32) {
+        match &mut self.grid {
+            Grid::Sparse(grid) => {
+                let value = grid.entry((x, y)).or_insert(0);
+                *value += 1;
+            }
+            Grid::Dense {
+                xmin,
+                ymin,
+                width,
+                cells,
+            } => {
+                let idx = (y - *ymin) as usize * *width + (x - *xmin) as usize;
+                cells[idx] += 1;
+            }
+            Grid::Sweep(_) => {}
+        }
+    }
+}
+
+fn cross(a: (i64, i64), b: (i64, i64)) -> i64 {
+    a.0 * b.1 - a.1 * b.0
+}
+
+// The lattice point where two non-parallel lines cross, without walking
+// either of them.
+fn crossing(a: &Line, b: &Line) -> Option<(i32, i32)> {
+    let (ax, ay) = a.start();
+    let (bx, by) = b.start();
+    let (u, v) = (a.step(), b.step());
+    let w = (bx as i64 - ax as i64, by as i64 - ay as i64);
+    let (sa, sb) = (a.size(), b.size());
+
+    let denom = cross(u, v);
+    if denom == 0 {
+        return None;
+    }
+
+    let (t, s) = (cross(w, v), cross(w, u));
+    if t % denom != 0 || s % denom != 0 {
+        return None;
+    }
+
+    let (t, s) = (t / denom, s / denom);
+    if (0..sa).contains(&t) && (0..sb).contains(&s) {
+        Some(((ax as i64 + t * u.0) as i32, (ay as i64 + t * u.1) as i32))
+    } else {
+        None
+    }
+}
+
+type Carrier = (i64, i64, i64);
+
+// The infinite line through a line as its primitive direction, pointing
+// right or up, and the offset shared by all its points. Lines on the same
+// carrier share its lattice.
+fn carrier(line: &Line) -> Carrier {
+    let (dx, dy) = line.step();
+    let (dx, dy) = if dx < 0 || (dx == 0 && dy < 0) {
+        (-dx, -dy)
+    } else {
+        (dx, dy)
+    };
+    let (x, y) = line.start();
+
+    (dx, dy, x as i64 * dy - y as i64 * dx)
+}
+
+// Index of a lattice point along its carrier.
+fn position(carrier: Carrier, (x, y): (i32, i32)) -> i64 {
+    match carrier {
+        (0, _, _) => y as i64,
+        (dx, _, _) => (x as i64).div_euclid(dx),
+    }
+}
+
+// Merges index intervals into disjoint `(first, last, count)` runs.
+fn runs(intervals: &[(i64, i64)]) -> Vec<(i64, i64, usize)> {
+    let mut events = intervals
+        .iter()
+        .flat_map(|(first, last)| [(*first, 1), (*last + 1, -1)])
+        .collect::<Vec<_>>();
+    events.sort_unstable();
+
+    let mut result = vec![];
+    let (mut from, mut count) = (0, 0i64);
+
+    for (at, delta) in events {
+        if count > 0 && at > from {
+            result.push((from, at - 1, count as usize));
+        }
+        count += delta;
+        from = at;
+    }
+
+    result
+}
+
+fn coverage(runs: &[(i64, i64, usize)], at: i64) -> usize {
+    match runs.get(runs.partition_point(|run| run.1 < at)) {
+        Some((first, _, count)) if *first <= at => *count,
+        _ => 0,
+    }
+}
+
+// Overlaps of collinear lines are counted as runs along their carrier, only
+// points where different carriers cross are materialised. Those are found
+// by comparing every pair of lines with overlapping x ranges, so inputs where
+// most x ranges overlap still take quadratic time.
+fn sweep(lines: &[Line], threshold: Threshold) -> usize {
+    let carriers = lines.iter().map(carrier).collect::<Vec<_>>();
+
+    let mut intervals: HashMap<Carrier, Vec<(i64, i64)>> = HashMap::new();
+    for (line, key) in lines.iter().zip(carriers.iter()) {
+        let (a, b) = (position(*key, line.start()), position(*key, line.end()));
+        intervals
+            .entry(*key)
+            .or_default()
+            .push((a.min(b), a.max(b)));
+    }
+
+    let runs = intervals
+        .into_iter()
+        .map(|(key, intervals)| (key, runs(&intervals)))
+        .collect::<HashMap<_, _>>();
+
+    let range = |l: &Line| {
+        let (x1, x2) = (l.start().0, l.end().0);
+        (x1.min(x2), x1.max(x2))
+    };
+
+    let mut order = (0..lines.len()).collect::<Vec<_>>();
+    order.sort_by_key(|idx| range(&lines[*idx]).0);
+
+    let mut crossings: HashMap<(i32, i32), HashSet<Carrier>> = HashMap::new();
+    let mut active: Vec<usize> = vec![];
+
+    for idx in order {
+        let (xmin, _) = range(&lines[idx]);
+        active.retain(|other| range(&lines[*other]).1 >= xmin);
+
+        for other in active
+            .iter()
+            .filter(|other| carriers[**other] != carriers[idx])
+        {
+            if let Some(point) = crossing(&lines[*other], &lines[idx]) {
+                let entry = crossings.entry(point).or_default();
+                entry.insert(carriers[*other]);
+                entry.insert(carriers[idx]);
+            }
+        }
+
+        active.push(idx);
+    }
+
+    let mut result = runs
+        .values()
+        .flatten()
+        .filter(|(_, _, count)| threshold.matches(*count))
+        .map(|(first, last, _)| (last - first + 1) as usize)
+        .sum::<usize>();
+
+    // crossing points were counted once per carrier with its own coverage
+    for (point, keys) in crossings {
+        let counts = keys
+            .iter()
+            .map(|key| coverage(&runs[key], position(*key, point)))
+            .collect::<Vec<_>>();
+
+        result -= counts.iter().filter(|c| threshold.matches(**c)).count();
+        if threshold.matches(counts.iter().sum()) {
+            result += 1;
+        }
+    }
+
+    result
+}
+
+#[derive(Debug, Clone)]
+enum Line {
+    Horizontal { x1: i32, x2: i32, y: i32 },
+    Vertical { x: i32, y1: i32, y2: i32 },
+    Diagonal { x1: i32, y1: i32, x2: i32, y2: i32 },
+    Sloped { x1: i32, y1: i32, x2: i32, y2: i32 },
+}
+
+fn gcd(a: i64, b: i64) -> i64 {
+    if b == 0 {
+        a
+    } else {
+        gcd(b, a % b)
+    }
+}
+
+impl Line {
+    pub fn parse(input: &str) -> Option<Self> {
+        let (x1, y1, x2, y2) = Self::coords(input)?;
+        Some(Self::new(x1, y1, x2, y2))
+    }
+
+    fn coords(input: &str) -> Option<(i32, i32, i32, i32)> {
+        lazy_static! {
+            static ref RE: Regex = Regex::new(r"^(\d+),(\d+)\s+->\s+(\d+),(\d+)$").unwrap();
+        }
+
+        let caps = RE.captures(input)?;
+
+        let x1 = caps.get(1)?.as_str().parse::<i32>().ok()?;
+        let y1 = caps.get(2)?.as_str().parse::<i32>().ok()?;
+        let x2 = caps.get(3)?.as_str().parse::<i32>().ok()?;
+        let y2 = caps.get(4)?.as_str().parse::<i32>().ok()?;
+
+        Some((x1, y1, x2, y2))
+    }
+
+    fn new(x1: i32, y1: i32, x2: i32, y2: i32) -> Self {
+        if x1 == x2 {
+            let x = x1;
+            Line::Vertical { x, y1, y2 }
+        } else if y1 == y2 {
+            let y = y1;
+            Line::Horizontal { x1, x2, y }
+        } else if (x1 - x2).abs() == (y1 - y2).abs() {
+            Line::Diagonal { x1, y1, x2, y2 }
+        } else {
+            Line::Sloped { x1, y1, x2, y2 }
+        }
+    }
+
+    fn orthogonal(&self) -> bool {
+        matches!(self, Self::Horizontal { .. } | Self::Vertical { .. })
+    }
+
+    fn sloped(&self) -> bool {
+        matches!(self, Self::Sloped { .. })
+    }
+
+    pub fn size(&self) -> i64 {
+        let span = |a: i32, b: i32| (a as i64 - b as i64).abs();
+
+        match *self {
+            Self::Horizontal { x1, x2, .. } => span(x1, x2) + 1,
+            Self::Vertical { y1, y2, .. } => span(y1, y2) + 1,
+            Self::Diagonal { x1, x2, .. } => span(x1, x2) + 1,
+            Self::Sloped { x1, y1, x2, y2 } => gcd(span(x1, x2), span(y1, y2)) + 1,
+        }
+    }
+
+    pub fn step(&self) -> (i64, i64) {
+        match *self {
+            Self::Horizontal { x1, x2, .. } => {
+                let dx = if x2 > x1 { 1 } else { -1 };
+                (dx, 0)
+            }
+            Self::Vertical { y1, y2, .. } => {
+                let dy = if y2 > y1 { 1 } else { -1 };
+                (0, dy)
+            }
+
+            Self::Diagonal { x1, y1, x2, y2 } => {
+                let dx = if x2 > x1 { 1 } else { -1 };
+                let dy = if y2 > y1 { 1 } else { -1 };
+                (dx, dy)
+            }
+
+            // the smallest step which still lands on integer points
+            Self::Sloped { x1, y1, x2, y2 } => {
+                let (dx, dy) = (x2 as i64 - x1 as i64, y2 as i64 - y1 as i64);
+                let g = gcd(dx.abs(), dy.abs());
+                (dx / g, dy / g)
+            }
+        }
+    }
+
+    pub fn end(&self) -> (i32, i32) {
+        let (x, y) = self.start();
+        let (dx, dy) = self.step();
+        let n = self.size() - 1;
+
+        ((x as i64 + n * dx) as i32, (y as i64 + n * dy) as i32)
+    }
+
+    pub fn start(&self) -> (i32, i32) {
+        match *self {
+            Self::Horizontal { x1, y, .. } => (x1, y),
+            Self::Vertical { x, y1, .. } => (x, y1),
+            Self::Diagonal { x1, y1, .. } => (x1, y1),
+            Self::Sloped { x1, y1, .. } => (x1, y1),
+        }
+    }
+}
+
+fn check(input: &str, strict: bool) -> Vec<Violation> {
+    let mut violations = vec![];
+
+    for (idx, raw) in input.lines().enumerate() {
+        match Line::coords(raw) {
+            None => violations.push(Violation::new(idx + 1, format!("can't parse {:?}", raw))),
+            Some((x1, y1, x2, y2)) if strict && Line::new(x1, y1, x2, y2).sloped() => {
+                let message = "line is not horizontal, vertical or diagonal (45°)";
+                violations.push(Violation::new(idx + 1, message))
+            }
+            _ => {}
+        }
+    }
+
+    violations
+}
+
+fn parse(input: &str) -> Result<Vec<Line>, Box<dyn Error>> {
+    let lines = input
+        .lines()
+        .map(Line::parse)
+        .collect::<Option<Vec<_>>>()
+        .ok_or("Invalid input")?;
+
+    Ok(lines)
+}
+
+// Orthogonal lines first for task A, then the rest for task B.
+fn overlaps(mut game: Game, lines: &[Line]) -> (usize, usize) {
+    for line in lines.iter().filter(|l| l.orthogonal()) {
+        game.add_line(line);
+    }
+
+    let result_a = game.result();
+
+    for line in lines.iter().filter(|l| !l.orthogonal()) {
+        game.add_line(line);
+    }
+
+    (result_a, game.result())
+}
+
+pub(crate) fn solve(input: &str) -> Result<(usize, usize), Box<dyn Error>> {
+    let lines = parse(input)?;
+
+    Ok(overlaps(Game::for_lines(&lines), &lines))
+}
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let filename = std::env::args().nth(1).ok_or("Invalid input")?;
+    let input = std::fs::read_to_string(filename)?;
+
+    // only lines along the eight compass directions, as in the original puzzle
+    let strict = std::env::args().any(|a| a == "--octilinear");
+
+    if std::env::args().any(|a| a == "--check") {
+        return check::report(&check(&input, strict));
+    }
+
+    let lines = parse(&input)?;
+
+    if let Some(idx) = lines.iter().position(|l| strict && l.sloped()) {
+        let message = format!(
+            "line {} is not horizontal, vertical or diagonal (45°)",
+            idx + 1
+        );
+        return Err(message.into());
+    }
+
+    let args = std::env::args().collect::<Vec<_>>();
+    let flagged = ["--at-least", "--exactly", "--backend"];
+
+    if !strict && !args.iter().any(|a| flagged.contains(&a.as_str())) {
+        let (result_a, result_b) = solve(&input)?;
+        println!("Task A: {}\nTask B: {}", result_a, result_b);
+        return Ok(());
+    }
+
+    let value = |flag: &str| {
+        args.iter()
+            .position(|a| a == flag)
+            .and_then(|idx| args.get(idx + 1))
+    };
+
+    let threshold = match (value("--at-least"), value("--exactly")) {
+        (Some(k), None) => Threshold::AtLeast(k.parse()?),
+        (None, Some(k)) => Threshold::Exactly(k.parse()?),
+        (None, None) => Threshold::AtLeast(2),
+        _ => return Err("Use either --at-least or --exactly".into()),
+    };
+
+    let game = match value("--backend").map(|b| b.as_str()) {
+        None => Game::for_lines(&lines),
+        Some("sparse") => Game::new(),
+        Some("sweep") => Game::sweep(),
+        Some(other) => return Err(format!("Unknown backend {}", other).into()),
+    };
+    let (result_a, result_b) = overlaps(game.with_threshold(threshold), &lines);
+
+    println!("Task A: {}\nTask B: {}", result_a, result_b);
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_sloped() {
+        let line = Line::parse("0,0 -> 6,4").unwrap();
+        assert!(line.sloped());
+        assert_eq!((line.size(), line.step()), (3, (3, 2)));
+
+        let line = Line::parse("7,1 -> 1,3").unwrap();
+        assert_eq!((line.size(), line.step()), (3, (-3, 1)));
+
+        let mut game = Game::new();
+        game.add_line(&Line::parse("0,0 -> 6,4").unwrap());
+        game.add_line(&Line::parse("3,0 -> 3,4").unwrap());
+        assert_eq!(game.result(), 1);
+    }
+
+    #[test]
+    fn test_backends() {
+        let lines = std::fs::read_to_string("input/2021/day05-demo.txt")
+            .unwrap()
+            .lines()
+            .chain(["0,0 -> 6,4", "6,4 -> 0,0", "9,0 -> 0,3", "1,1 -> 1,1"])
+            .map(|l| Line::parse(l).unwrap())
+            .collect::<Vec<_>>();
+
+        let thresholds = [
+            Threshold::AtLeast(1),
+            Threshold::AtLeast(2),
+            Threshold::AtLeast(3),
+            Threshold::Exactly(1),
+            Threshold::Exactly(2),
+        ];
+
+        for threshold in thresholds {
+            let mut games = [Game::new(), Game::for_lines(&lines), Game::sweep()]
+                .map(|game| game.with_threshold(threshold));
+
+            for game in games.iter_mut() {
+                lines.iter().for_each(|line| game.add_line(line));
+            }
+
+            assert!(matches!(games[1].grid, Grid::Dense { .. }));
+            let results = games.iter().map(|g| g.result()).collect::<Vec<_>>();
+            assert_eq!(results[0], results[1], "{:?}", threshold);
+            assert_eq!(results[0], results[2], "{:?}", threshold);
+        }
+    }
+
+    #[test]
+    fn test_huge_coords() {
+        let lines = [
+            "0,0 -> 1000000000,1000000000",
+            "0,1000000000 -> 1000000000,0",
+        ]
+        .iter()
+        .map(|l| Line::parse(l).unwrap())
+        .collect::<Vec<_>>();
+
+        let mut game = Game::for_lines(&lines);
+        lines.iter().for_each(|line| game.add_line(line));
+
+        assert!(matches!(game.grid, Grid::Sweep(_)));
+        assert_eq!(game.result(), 1);
+
+        let lines = ["0,0 -> 200000000,0", "200000000,0 -> 0,0", "5,0 -> 5,5"]
+            .iter()
+            .map(|l| Line::parse(l).unwrap())
+            .collect::<Vec<_>>();
+
+        for (threshold, expected) in [
+            (Threshold::AtLeast(2), 200_000_001),
+            (Threshold::Exactly(2), 200_000_000),
+            (Threshold::Exactly(3), 1),
+            (Threshold::Exactly(1), 5),
+        ] {
+            let mut game = Game::for_lines(&lines).with_threshold(threshold);
+            lines.iter().for_each(|line| game.add_line(line));
+            assert_eq!(game.result(), expected, "{:?}", threshold);
+        }
+
+        let lines = [
+            "0,0 -> 2147483647,0",
+            "2147483647,0 -> 0,0",
+            "2147483647,5 -> 2147483647,0",
+            "0,2147483647 -> 2147483647,0",
+        ]
+        .iter()
+        .map(|l| Line::parse(l).unwrap())
+        .collect::<Vec<_>>();
+        assert_eq!(lines[0].size(), 1 << 31);
+        assert_eq!(lines[3].end(), (i32::MAX, 0));
+
+        let mut game = Game::for_lines(&lines);
+        lines.iter().for_each(|line| game.add_line(line));
+        assert_eq!(game.result(), 1 << 31);
+    }
+}
@@ -0,0 +1,363 @@
+use advent2021::bigint::BigUint;
+use std::error::Error;
+use std::fmt;
+
+const T1: usize = 80;
+const T2: usize = 256;
+const OVERFLOW: &str = "Population overflows, use --project";
+
+// A parent restarts its timer at `cycle - 1`, a newborn starts `delay` days
+// later than that.
+#[derive(Debug, Clone, Copy)]
+struct Species {
+    cycle: usize,
+    delay: usize,
+}
+
+impl Species {
+    fn timers(&self) -> usize {
+        self.cycle + self.delay
+    }
+}
+
+impl Default for Species {
+    fn default() -> Self {
+        Self { cycle: 7, delay: 2 }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+struct Population {
+    day: usize,
+    buckets: Vec<u128>,
+    total: u128,
+}
+
+impl Population {
+    fn total(&self) -> u128 {
+        self.total
+    }
+}
+
+impl fmt::Display for Population {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "day {}: {}", self.day, self.total)?;
+        for (timer, count) in self.buckets.iter().enumerate() {
+            writeln!(f, "  timer {:2}: {}", timer, count)?;
+        }
+
+        Ok(())
+    }
+}
+
+trait Arithmetic {
+    type Value: Clone;
+
+    fn value(&self, n: u64) -> Self::Value;
+    fn add(&self, a: &Self::Value, b: &Self::Value) -> Self::Value;
+    fn mul(&self, a: &Self::Value, b: &Self::Value) -> Self::Value;
+}
+
+struct Exact;
+
+impl Arithmetic for Exact {
+    type Value = BigUint;
+
+    fn value(&self, n: u64) -> BigUint {
+        BigUint::from(n)
+    }
+
+    fn add(&self, a: &BigUint, b: &BigUint) -> BigUint {
+        a + b
+    }
+
+    fn mul(&self, a: &BigUint, b: &BigUint) -> BigUint {
+        a * b
+    }
+}
+
+struct Modular(u64);
+
+impl Arithmetic for Modular {
+    type Value = u64;
+
+    fn value(&self, n: u64) -> u64 {
+        n % self.0
+    }
+
+    fn add(&self, a: &u64, b: &u64) -> u64 {
+        ((*a as u128 + *b as u128) % self.0 as u128) as u64
+    }
+
+    fn mul(&self, a: &u64, b: &u64) -> u64 {
+        ((*a as u128 * *b as u128) % self.0 as u128) as u64
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Matrix<V> {
+    size: usize,
+    cells: Vec<V>,
+}
+
+impl<V: Clone> Matrix<V> {
+    fn identity<A: Arithmetic<Value = V>>(size: usize, arith: &A) -> Self {
+        let cells = (0..size * size)
+            .map(|idx| arith.value((idx / size == idx % size) as u64))
+            .collect();
+
+        Self { size, cells }
+    }
+
+    fn mul<A: Arithmetic<Value = V>>(&self, other: &Self, arith: &A) -> Self {
+        let n = self.size;
+        let mut cells = Vec::with_capacity(n * n);
+
+        for row in 0..n {
+            for col in 0..n {
+                let cell = (0..n).fold(arith.value(0), |acc, k| {
+                    let product = arith.mul(&self.cells[row * n + k], &other.cells[k * n + col]);
+                    arith.add(&acc, &product)
+                });
+                cells.push(cell);
+            }
+        }
+
+        Self { size: n, cells }
+    }
+
+    fn pow<A: Arithmetic<Value = V>>(&self, mut exp: u64, arith: &A) -> Self {
+        let mut result = Self::identity(self.size, arith);
+        let mut base = self.clone();
+
+        while exp > 0 {
+            if exp & 1 == 1 {
+                result = result.mul(&base, arith);
+            }
+            base = base.mul(&base, arith);
+            exp >>= 1;
+        }
+
+        result
+    }
+
+    fn apply<A: Arithmetic<Value = V>>(&self, vector: &[V], arith: &A) -> Vec<V> {
+        (0..self.size)
+            .map(|row| {
+                vector
+                    .iter()
+                    .enumerate()
+                    .fold(arith.value(0), |acc, (col, v)| {
+                        arith.add(&acc, &arith.mul(&self.cells[row * self.size + col], v))
+                    })
+            })
+            .collect()
+    }
+}
+
+#[derive(Clone)]
+struct Game {
+    species: Species,
+    buckets: Vec<u128>,
+}
+
+impl Game {
+    pub fn parse(input: &str, species: Species) -> Result<Self, Box<dyn Error>> {
+        if species.cycle == 0 {
+            return Err("Cycle must be at least one day".into());
+        }
+
+        let mut buckets = vec![0; species.timers()];
+
+        for n in input.trim().split(',') {
+            let timer = n
+                .trim()
+                .parse::<usize>()
+                .map_err(|_| format!("Invalid value: {}", n))?;
+            let bucket = buckets
+                .get_mut(timer)
+                .ok_or_else(|| format!("Timer out of range: {}", timer))?;
+            *bucket += 1;
+        }
+
+        Ok(Self { species, buckets })
+    }
+
+    fn step(&mut self) -> Option<()> {
+        let parents = self.buckets[0];
+        self.buckets.rotate_left(1);
+
+        let bucket = &mut self.buckets[self.species.cycle - 1];
+        *bucket = bucket.checked_add(parents)?;
+        Some(())
+    }
+
+    // One day as a matrix: every timer moves one bucket down, parents
+    // restart at `cycle - 1` and newborns appear in the last bucket.
+    fn transition<A: Arithmetic>(&self, arith: &A) -> Matrix<A::Value> {
+        let n = self.buckets.len();
+        let mut counts = vec![0u64; n * n];
+
+        for timer in 1..n {
+            counts[(timer - 1) * n + timer] += 1;
+        }
+        counts[(self.species.cycle - 1) * n] += 1;
+        counts[(n - 1) * n] += 1;
+
+        let cells = counts.iter().map(|c| arith.value(*c)).collect();
+        Matrix { size: n, cells }
+    }
+
+    // Per-timer counts after `day` days in O(timers^3 * log(day)) operations.
+    pub fn projection<A: Arithmetic>(&self, day: u64, arith: &A) -> Vec<A::Value> {
+        let buckets = self
+            .buckets
+            .iter()
+            .map(|b| arith.value(*b as u64))
+            .collect::<Vec<_>>();
+
+        self.transition(arith)
+            .pow(day, arith)
+            .apply(&buckets, arith)
+    }
+
+    // None once a bucket or the total no longer fits, `projection` has no
+    // such limit.
+    pub fn population_at(&self, day: usize) -> Option<Population> {
+        let mut game = self.clone();
+        for _ in 0..day {
+            game.step()?;
+        }
+
+        let total = game
+            .buckets
+            .iter()
+            .try_fold(0u128, |acc, b| acc.checked_add(*b))?;
+
+        Some(Population {
+            day,
+            buckets: game.buckets,
+            total,
+        })
+    }
+}
+
+fn totals(game: &Game) -> Result<(u128, u128), Box<dyn Error>> {
+    let r1 = game.population_at(T1).ok_or(OVERFLOW)?.total();
+    let r2 = game.population_at(T2).ok_or(OVERFLOW)?.total();
+
+    Ok((r1, r2))
+}
+
+pub(crate) fn solve(input: &str) -> Result<(u128, u128), Box<dyn Error>> {
+    totals(&Game::parse(input, Species::default())?)
+}
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let args = std::env::args().collect::<Vec<_>>();
+    let filename = args.get(1).ok_or("Invalid input")?;
+    let value = |flag: &str| -> Result<Option<usize>, Box<dyn Error>> {
+        match args.iter().position(|a| a == flag) {
+            Some(idx) => Ok(Some(args.get(idx + 1).ok_or("Missing value")?.parse()?)),
+            None => Ok(None),
+        }
+    };
+
+    let defaults = Species::default();
+    let species = Species {
+        cycle: value("--cycle")?.unwrap_or(defaults.cycle),
+        delay: value("--delay")?.unwrap_or(defaults.delay),
+    };
+
+    let input = std::fs::read_to_string(filename)?;
+    let game = Game::parse(&input, species)?;
+
+    if let Some(idx) = args.iter().position(|a| a == "--project") {
+        let day = args.get(idx + 1).ok_or("Missing day")?.parse::<u64>()?;
+
+        match value("--modulo")? {
+            Some(0) => return Err("Modulus must be positive".into()),
+            Some(modulus) => {
+                let arith = Modular(modulus as u64);
+                let buckets = game.projection(day, &arith);
+                let total = buckets.iter().fold(0, |acc, b| arith.add(&acc, b));
+                println!("day {}: {} (mod {})", day, total, modulus);
+            }
+            None => {
+                let buckets = game.projection(day, &Exact);
+                let total = buckets.iter().fold(BigUint::zero(), |acc, b| &acc + b);
+                println!("day {}: {}", day, total);
+            }
+        }
+
+        return Ok(());
+    }
+
+    if let Some(day) = value("--day")? {
+        print!("{}", game.population_at(day).ok_or(OVERFLOW)?);
+        return Ok(());
+    }
+
+    let (r1, r2) = match (value("--cycle")?, value("--delay")?) {
+        (None, None) => solve(&input)?,
+        _ => totals(&game)?,
+    };
+
+    println!("Task A: {}\nTask B: {}", r1, r2);
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_population() {
+        let game = Game::parse("3,4,3,1,2 \n", Species::default()).unwrap();
+        assert_eq!(game.population_at(18).unwrap().total(), 26);
+        assert!(game.population_at(2000).is_none());
+
+        let population = game.population_at(3).unwrap();
+        assert_eq!(population.buckets, vec![2, 1, 0, 0, 0, 1, 1, 1, 1]);
+
+        let species = Species {
+            cycle: 3,
+            delay: 10,
+        };
+        let game = Game::parse("12, 0", species).unwrap();
+        assert_eq!(game.population_at(1).unwrap().buckets[12], 1);
+        assert_eq!(game.population_at(1).unwrap().buckets[2], 1);
+        assert!(Game::parse("13", species).is_err());
+    }
+
+    #[test]
+    fn test_projection() {
+        let game = Game::parse("3,4,3,1,2", Species::default()).unwrap();
+
+        let exact = game.projection(256, &Exact);
+        let total = exact.iter().fold(BigUint::zero(), |acc, b| &acc + b);
+        assert_eq!(total.to_string(), "26984457539");
+
+        let stepped = game.population_at(80).unwrap().buckets;
+        let projected = game.projection(80, &Modular(u64::MAX));
+        assert_eq!(
+            projected,
+            stepped.iter().map(|b| *b as u64).collect::<Vec<_>>()
+        );
+
+        let modulus = 1_000_000_007;
+        let projected = game.projection(256, &Modular(modulus));
+        let total = projected.iter().sum::<u64>() % modulus;
+        assert_eq!(total, 26984457539 % modulus);
+
+        let species = Species { cycle: 3, delay: 0 };
+        let game = Game::parse("0,1,2", species).unwrap();
+        let projected = game.projection(5, &Modular(1000));
+        let stepped = game.population_at(5).unwrap().buckets;
+        assert_eq!(
+            projected,
+            stepped.iter().map(|b| *b as u64).collect::<Vec<_>>()
+        );
+    }
+}
@@ -0,0 +1,270 @@
+mod swarm;
+
+use std::error::Error;
+use swarm::{Metric, Swarm};
+
+#[derive(Debug, PartialEq)]
+struct Alignment {
+    fuel: i32,
+    positions: Vec<i32>,
+}
+
+fn linear(a: i32, b: i32) -> i32 {
+    (a - b).abs()
+}
+
+fn triangular(a: i32, b: i32) -> i32 {
+    (a - b).abs() * ((a - b).abs() + 1) / 2
+}
+
+#[derive(Debug)]
+struct Game {
+    crabs: Vec<i32>,
+}
+
+impl Game {
+    pub fn parse(input: &str) -> Result<Self, Box<dyn Error>> {
+        let crabs = input
+            .trim()
+            .split(',')
+            .map(|n| n.trim().parse::<i32>())
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Self { crabs })
+    }
+
+    pub fn fuel_to<F>(&self, target: i32, fuel_fx: F) -> i32
+    where
+        F: Fn(i32, i32) -> i32,
+    {
+        self.crabs.iter().map(|c| fuel_fx(*c, target)).sum()
+    }
+
+    pub fn max(&self) -> Option<i32> {
+        self.crabs.iter().max().copied()
+    }
+
+    pub fn min(&self) -> Option<i32> {
+        self.crabs.iter().min().copied()
+    }
+
+    // Every position between the two middle crabs is a median.
+    pub fn align_linear(&self) -> Option<Alignment> {
+        let mut sorted = self.crabs.clone();
+        sorted.sort_unstable();
+
+        let lo = *sorted.get((sorted.len().checked_sub(1)?) / 2)?;
+        let hi = *sorted.get(sorted.len() / 2)?;
+
+        Some(Alignment {
+            fuel: self.fuel_to(lo, linear),
+            positions: (lo..=hi).collect(),
+        })
+    }
+
+    // The real optimum lies within 1/2 of the mean, so the integer ones are
+    // among the few positions around it.
+    pub fn align_triangular(&self) -> Option<Alignment> {
+        let count = self.crabs.len() as i64;
+        let sum = self.crabs.iter().map(|c| *c as i64).sum::<i64>();
+        if count == 0 {
+            return None;
+        }
+        let mean = sum.div_euclid(count) as i32;
+
+        self.best(mean - 1..=mean + 2, triangular)
+    }
+
+    // Ternary search over positions, valid for any convex fuel function.
+    pub fn align_convex<F>(&self, fuel_fx: F) -> Option<Alignment>
+    where
+        F: Fn(i32, i32) -> i32 + Copy,
+    {
+        let (mut lo, mut hi) = (self.min()?, self.max()?);
+
+        while hi - lo > 2 {
+            let m1 = lo + (hi - lo) / 3;
+            let m2 = hi - (hi - lo) / 3;
+            let (f1, f2) = (self.fuel_to(m1, fuel_fx), self.fuel_to(m2, fuel_fx));
+
+            if f1 < f2 {
+                hi = m2 - 1;
+            } else if f1 > f2 {
+                lo = m1 + 1;
+            } else {
+                // a convex function is only flat around its minimum
+                lo = m1;
+                hi = m2;
+            }
+        }
+
+        let Alignment { fuel, positions } = self.best(lo..=hi, fuel_fx)?;
+        let (first, last) = (positions[0], positions[positions.len() - 1]);
+
+        let left = (self.min()?..first)
+            .rev()
+            .take_while(|p| self.fuel_to(*p, fuel_fx) == fuel);
+        let right = (last + 1..=self.max()?).take_while(|p| self.fuel_to(*p, fuel_fx) == fuel);
+
+        let mut positions = left.chain(positions).chain(right).collect::<Vec<_>>();
+        positions.sort_unstable();
+
+        Some(Alignment { fuel, positions })
+    }
+
+    // Checks every position between the outermost crabs.
+    pub fn align_brute<F>(&self, fuel_fx: F) -> Option<Alignment>
+    where
+        F: Fn(i32, i32) -> i32 + Copy,
+    {
+        self.best(self.min()?..=self.max()?, fuel_fx)
+    }
+
+    fn best<F>(&self, range: impl Iterator<Item = i32>, fuel_fx: F) -> Option<Alignment>
+    where
+        F: Fn(i32, i32) -> i32 + Copy,
+    {
+        let fuel = range
+            .map(|p| (p, self.fuel_to(p, fuel_fx)))
+            .collect::<Vec<_>>();
+        let min = fuel.iter().map(|(_, f)| *f).min()?;
+
+        Some(Alignment {
+            fuel: min,
+            positions: fuel
+                .iter()
+                .filter(|(_, f)| *f == min)
+                .map(|(p, _)| *p)
+                .collect(),
+        })
+    }
+}
+
+fn swarm(input: &str) -> Result<(), Box<dyn Error>> {
+    let args = std::env::args().collect::<Vec<_>>();
+    let dims = args
+        .iter()
+        .position(|a| a == "--dims")
+        .map(|idx| args.get(idx + 1).and_then(|d| d.parse::<usize>().ok()))
+        .map(|dims| dims.ok_or("Invalid dims"))
+        .transpose()?;
+
+    let swarm = Swarm::parse(input, dims)?;
+    let task_a = swarm.meet(Metric::Linear);
+    let task_b = swarm.meet(Metric::Triangular);
+
+    if std::env::args().any(|a| a == "--brute") {
+        for (metric, expected) in [(Metric::Linear, &task_a), (Metric::Triangular, &task_b)] {
+            let brute = swarm.brute(metric);
+            if brute.fuel != expected.fuel {
+                let message = format!("Brute force disagrees: {} vs {}", brute, expected);
+                return Err(message.into());
+            }
+        }
+    }
+
+    println!("Meeting A at {}, B at {}", task_a, task_b);
+    println!("Task A: {}, Task B: {}", task_a.fuel, task_b.fuel);
+
+    Ok(())
+}
+
+fn parse(input: &str) -> Result<Game, Box<dyn Error>> {
+    input
+        .lines()
+        .next()
+        .ok_or_else(|| "Empth input".into())
+        .and_then(Game::parse)
+}
+
+pub(crate) fn solve(input: &str) -> Result<(i32, i32), Box<dyn Error>> {
+    let game = parse(input)?;
+
+    let task_a = game.align_linear().ok_or("Empty input")?;
+    let task_b = game.align_triangular().ok_or("Empty input")?;
+
+    Ok((task_a.fuel, task_b.fuel))
+}
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let filename = std::env::args().nth(1).ok_or("Invalid input")?;
+    let input = std::fs::read_to_string(filename)?;
+
+    if std::env::args().any(|a| a == "--swarm") {
+        return swarm(&input);
+    }
+
+    if !std::env::args().any(|a| a == "--brute" || a == "--positions") {
+        let (task_a, task_b) = solve(&input)?;
+        println!("Task A: {}, Task B: {}", task_a, task_b);
+        return Ok(());
+    }
+
+    let game = parse(&input)?;
+
+    let task_a = game.align_linear().ok_or("Empty input")?;
+    let task_b = game.align_triangular().ok_or("Empty input")?;
+
+    if std::env::args().any(|a| a == "--brute") {
+        for (fuel_fx, expected) in [
+            (linear as fn(i32, i32) -> i32, &task_a),
+            (triangular, &task_b),
+        ] {
+            let convex = game.align_convex(fuel_fx).ok_or("Empty input")?;
+            let brute = game.align_brute(fuel_fx).ok_or("Empty input")?;
+
+            if &convex != expected || &brute != expected {
+                let message = format!(
+                    "Brute force disagrees: {:?} vs {:?} and {:?}",
+                    brute, expected, convex
+                );
+                return Err(message.into());
+            }
+        }
+    }
+
+    if std::env::args().any(|a| a == "--positions") {
+        println!(
+            "Positions A {:?}, B {:?}",
+            task_a.positions, task_b.positions
+        );
+    }
+
+    println!("Task A: {}, Task B: {}", task_a.fuel, task_b.fuel);
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_alignment() {
+        let game = Game::parse("16,1,2,0,4,2,7,1,2,14").unwrap();
+
+        let expected = Alignment {
+            fuel: 37,
+            positions: vec![2],
+        };
+        assert_eq!(game.align_linear(), Some(expected));
+        assert_eq!(game.align_triangular().map(|a| a.positions), Some(vec![5]));
+
+        for fuel_fx in [linear, triangular] {
+            assert_eq!(game.align_convex(fuel_fx), game.align_brute(fuel_fx));
+        }
+
+        let game = Game::parse("1,5").unwrap();
+        let expected = Alignment {
+            fuel: 4,
+            positions: vec![1, 2, 3, 4, 5],
+        };
+        assert_eq!(game.align_linear(), Some(expected));
+        assert_eq!(game.align_convex(linear).unwrap().positions.len(), 5);
+        assert_eq!(game.align_triangular(), game.align_brute(triangular));
+
+        let game = Game::parse("-10,-1,-1").unwrap();
+        assert_eq!(game.align_triangular(), game.align_brute(triangular));
+        assert_eq!(Game { crabs: vec![] }.align_triangular(), None);
+    }
+}
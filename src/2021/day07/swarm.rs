@@ -0,0 +1,323 @@
+use std::error::Error;
+use std::fmt;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Metric {
+    Linear,
+    Triangular,
+}
+
+impl Metric {
+    // Crabs move one axis step at a time, so both metrics use the L1 distance.
+    fn cost(&self, from: &[i64], to: &[i64]) -> i64 {
+        let distance = from
+            .iter()
+            .zip(to.iter())
+            .map(|(a, b)| (a - b).abs())
+            .sum::<i64>();
+
+        match self {
+            Metric::Linear => distance,
+            Metric::Triangular => distance * (distance + 1) / 2,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Meeting {
+    pub point: Vec<i64>,
+    pub fuel: i64,
+}
+
+impl fmt::Display for Meeting {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let coords = self.point.iter().map(|c| c.to_string()).collect::<Vec<_>>();
+        write!(f, "({}) for {} fuel", coords.join(", "), self.fuel)
+    }
+}
+
+#[derive(Debug)]
+struct Crab {
+    position: Vec<i64>,
+    weight: i64,
+}
+
+impl Crab {
+    // `x,y[,z][ *weight]`
+    fn parse(line: &str) -> Result<Self, Box<dyn Error>> {
+        let (coords, weight) = match line.split_once('*') {
+            Some((coords, weight)) => (coords, weight.trim().parse::<i64>()?),
+            None => (line, 1),
+        };
+
+        if weight <= 0 {
+            return Err(format!("Weight must be positive: {}", line).into());
+        }
+
+        let position = coords
+            .trim()
+            .split(',')
+            .map(|c| c.trim().parse::<i64>())
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Self { position, weight })
+    }
+}
+
+// Crabs at integer coordinates of any dimension, each burning `weight` times
+// the fuel of a single crab.
+#[derive(Debug)]
+pub struct Swarm {
+    dims: usize,
+    crabs: Vec<Crab>,
+}
+
+impl Swarm {
+    // One crab with `dims` coordinates per line, or without `dims` the plain
+    // day07 format with every crab on a single line of the x axis.
+    pub fn parse(input: &str, dims: Option<usize>) -> Result<Self, Box<dyn Error>> {
+        let lines = input
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty())
+            .collect::<Vec<_>>();
+
+        let (crabs, dims) = match (&lines[..], dims) {
+            ([], _) => return Err("Empty input".into()),
+            (_, Some(dims)) if !(1..=3).contains(&dims) => {
+                return Err(format!("Crabs need 1 to 3 coordinates, not {}", dims).into())
+            }
+            (_, Some(dims)) => {
+                let crabs = lines
+                    .iter()
+                    .map(|line| Crab::parse(line))
+                    .collect::<Result<Vec<_>, _>>()?;
+                (crabs, dims)
+            }
+            ([line], None) => {
+                let crabs = line
+                    .split(',')
+                    .map(|c| Crab::parse(c).map_err(|_| format!("Invalid crab: {}", c)))
+                    .collect::<Result<Vec<_>, _>>()?;
+                (crabs, 1)
+            }
+            (_, None) => return Err("One crab per line needs --dims".into()),
+        };
+
+        if let Some(idx) = crabs.iter().position(|c| c.position.len() != dims) {
+            return Err(format!("Crab {} has not {} coordinates", idx + 1, dims).into());
+        }
+
+        Ok(Self { dims, crabs })
+    }
+
+    pub fn fuel_to(&self, point: &[i64], metric: Metric) -> i64 {
+        self.crabs
+            .iter()
+            .map(|c| c.weight * metric.cost(&c.position, point))
+            .sum()
+    }
+
+    fn bounds(&self, axis: usize) -> (i64, i64) {
+        let coords = self.crabs.iter().map(|c| c.position[axis]);
+        (coords.clone().min().unwrap_or(0), coords.max().unwrap_or(0))
+    }
+
+    pub fn meet(&self, metric: Metric) -> Meeting {
+        let point = match metric {
+            Metric::Linear => (0..self.dims).map(|axis| self.median(axis)).collect(),
+            Metric::Triangular => self.descend(vec![], metric),
+        };
+        let point = self.polish(point, metric);
+
+        Meeting {
+            fuel: self.fuel_to(&point, metric),
+            point,
+        }
+    }
+
+    // The L1 cost splits into independent axes, each minimised by its
+    // weighted median.
+    fn median(&self, axis: usize) -> i64 {
+        let mut coords = self
+            .crabs
+            .iter()
+            .map(|c| (c.position[axis], c.weight))
+            .collect::<Vec<_>>();
+        coords.sort_unstable();
+
+        let total = coords.iter().map(|(_, w)| w).sum::<i64>();
+        let mut seen = 0;
+
+        for (coord, weight) in coords {
+            seen += weight;
+            if 2 * seen >= total {
+                return coord;
+            }
+        }
+
+        0
+    }
+
+    // Nested ternary search: one axis at a time, with the remaining axes
+    // optimised for every probe.
+    fn descend(&self, prefix: Vec<i64>, metric: Metric) -> Vec<i64> {
+        if prefix.len() == self.dims {
+            return prefix;
+        }
+
+        let probe = |value: i64| {
+            let mut point = prefix.clone();
+            point.push(value);
+            let point = self.descend(point, metric);
+            (self.fuel_to(&point, metric), point)
+        };
+
+        let (mut lo, mut hi) = self.bounds(prefix.len());
+        while hi - lo > 2 {
+            let m1 = lo + (hi - lo) / 3;
+            let m2 = hi - (hi - lo) / 3;
+            let (f1, f2) = (probe(m1).0, probe(m2).0);
+
+            if f1 < f2 {
+                hi = m2 - 1;
+            } else if f1 > f2 {
+                lo = m1 + 1;
+            } else {
+                lo = m1;
+                hi = m2;
+            }
+        }
+
+        (lo..=hi)
+            .map(probe)
+            .min()
+            .map(|(_, point)| point)
+            .unwrap_or(prefix)
+    }
+
+    // Steps to any cheaper neighbour, diagonals included, until none is left.
+    fn polish(&self, mut point: Vec<i64>, metric: Metric) -> Vec<i64> {
+        let directions = (0..3usize.pow(self.dims as u32))
+            .map(|mut code| {
+                (0..self.dims)
+                    .map(|_| {
+                        let step = (code % 3) as i64 - 1;
+                        code /= 3;
+                        step
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .filter(|dir| dir.iter().any(|step| *step != 0))
+            .collect::<Vec<_>>();
+
+        let mut fuel = self.fuel_to(&point, metric);
+        loop {
+            let better = directions
+                .iter()
+                .map(|dir| {
+                    point
+                        .iter()
+                        .zip(dir)
+                        .map(|(c, s)| c + s)
+                        .collect::<Vec<_>>()
+                })
+                .map(|next| (self.fuel_to(&next, metric), next))
+                .filter(|(next_fuel, _)| *next_fuel < fuel)
+                .min();
+
+            match better {
+                Some((next_fuel, next)) => {
+                    fuel = next_fuel;
+                    point = next;
+                }
+                None => return point,
+            }
+        }
+    }
+
+    // Checks every point of the bounding box, the smallest one wins ties.
+    pub fn brute(&self, metric: Metric) -> Meeting {
+        let mut points = vec![vec![]];
+        for axis in 0..self.dims {
+            let (lo, hi) = self.bounds(axis);
+            points = points
+                .iter()
+                .flat_map(|point: &Vec<i64>| {
+                    (lo..=hi).map(move |c| {
+                        let mut next = point.clone();
+                        next.push(c);
+                        next
+                    })
+                })
+                .collect();
+        }
+
+        points
+            .into_iter()
+            .map(|point| (self.fuel_to(&point, metric), point))
+            .min()
+            .map(|(fuel, point)| Meeting { point, fuel })
+            .unwrap_or(Meeting {
+                point: vec![],
+                fuel: 0,
+            })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_swarm() {
+        for (input, dims) in [
+            ("16,1,2,0,4,2,7,1,2,14\n", None),
+            ("16\n1\n2\n0\n4\n2\n7\n1\n2\n14", Some(1)),
+        ] {
+            let swarm = Swarm::parse(input, dims).unwrap();
+            assert_eq!(swarm.dims, 1);
+            assert_eq!(swarm.meet(Metric::Linear).fuel, 37);
+            assert_eq!(swarm.meet(Metric::Triangular).fuel, 168);
+        }
+
+        assert!(Swarm::parse("16\n1\n2", None).is_err());
+        assert!(Swarm::parse("1,2,3,4\n5,6,7,8", Some(4)).is_err());
+        assert!(Swarm::parse("1,2 *x,3", None).is_err());
+        assert!(Swarm::parse("1,2 *2", Some(3)).is_err());
+
+        let swarm = Swarm::parse("3,4 *2", Some(2)).unwrap();
+        assert_eq!(swarm.dims, 2);
+        assert_eq!(swarm.crabs[0].weight, 2);
+        assert_eq!(
+            swarm.meet(Metric::Triangular),
+            Meeting {
+                point: vec![3, 4],
+                fuel: 0
+            }
+        );
+
+        let swarm = Swarm::parse("3,4 *2", None).unwrap();
+        assert_eq!((swarm.dims, swarm.crabs.len()), (1, 2));
+
+        let swarm = Swarm::parse("0,0 *3\n4,0\n0,6\n5,5 *2\n1,7,", Some(2)).err();
+        assert!(swarm.is_some());
+
+        let swarm = Swarm::parse("0,0 *3\n4,0\n0,6\n5,5 *2\n1,7", Some(2)).unwrap();
+        let expected = Meeting {
+            point: vec![0, 0],
+            fuel: 4 + 6 + 2 * 10 + 8,
+        };
+        assert_eq!(swarm.meet(Metric::Linear), expected);
+
+        for metric in [Metric::Linear, Metric::Triangular] {
+            assert_eq!(swarm.meet(metric).fuel, swarm.brute(metric).fuel);
+        }
+
+        let swarm = Swarm::parse("0,0,0\n3,1,2 *2\n-2,4,1\n1,1,5", Some(3)).unwrap();
+        assert_eq!(
+            swarm.meet(Metric::Triangular).fuel,
+            swarm.brute(Metric::Triangular).fuel
+        );
+    }
+}
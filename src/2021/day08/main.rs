@@ -1,7 +1,9 @@
+use advent2021::repl::{self, Session};
 use std::{
     collections::{BTreeSet, HashMap},
     convert::{TryFrom, TryInto},
     error::Error,
+    fmt,
     ops::{Rem, Sub},
 };
 
@@ -33,6 +35,20 @@ impl TryFrom<char> for Digit {
     }
 }
 
+impl Digit {
+    pub fn to_char(self) -> char {
+        match self {
+            Self::A => 'a',
+            Self::B => 'b',
+            Self::C => 'c',
+            Self::D => 'd',
+            Self::E => 'e',
+            Self::F => 'f',
+            Self::G => 'g',
+        }
+    }
+}
+
 #[derive(Debug, PartialEq, Eq, Hash, Clone)]
 struct Pattern {
     inner: BTreeSet<Digit>,
@@ -44,6 +60,13 @@ impl Pattern {
     }
 }
 
+impl fmt::Display for Pattern {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let chars = self.inner.iter().map(|d| d.to_char()).collect::<String>();
+        write!(f, "{}", chars)
+    }
+}
+
 impl Sub for &Pattern {
     type Output = Pattern;
 
@@ -76,7 +99,6 @@ impl TryFrom<&str> for Pattern {
 
 #[derive(Debug)]
 struct Input {
-    left: Vec<Pattern>,
     right: Vec<Pattern>,
     decoder: HashMap<Pattern, usize>,
 }
@@ -91,18 +113,12 @@ impl TryFrom<&str> for Input {
                 .collect::<Result<Vec<_>, _>>()
         });
 
-        let left = parts.next().unwrap_or_else(|| Err("Left part not found"))?;
-        let right = parts
-            .next()
-            .unwrap_or_else(|| Err("Right part not found"))?;
+        let left = parts.next().unwrap_or(Err("Left part not found"))?;
+        let right = parts.next().unwrap_or(Err("Right part not found"))?;
 
-        let decoder = decode(left.as_ref()).ok_or_else(|| "Can't decode")?;
+        let decoder = decode(left.as_ref()).ok_or("Can't decode")?;
 
-        Ok(Self {
-            left,
-            right,
-            decoder,
-        })
+        Ok(Self { right, decoder })
     }
 }
 
@@ -157,33 +173,109 @@ fn decode(input: &[Pattern]) -> Option<HashMap<Pattern, usize>> {
     Some(decoded)
 }
 
-fn main() -> Result<(), Box<dyn Error>> {
-    let filename = std::env::args().nth(1).ok_or("Invalid input")?;
-    let raw = std::fs::read_to_string(filename)?;
+struct Repl {
+    input: Vec<Input>,
+    current: usize,
+}
 
-    let result_a = raw
-        .lines()
-        .map(|l| l.split(" | ").skip(1).next().unwrap())
-        .map(|l| l.split(" ").map(|p| p.len()))
-        .flatten()
-        .filter(|n| n == &2 || n == &4 || n == &3 || n == &7)
-        .count();
+impl Repl {
+    pub fn new(input: Vec<Input>) -> Self {
+        Self { input, current: 0 }
+    }
+
+    fn entry(&self) -> Result<&Input, Box<dyn Error>> {
+        self.input
+            .get(self.current)
+            .ok_or_else(|| "Empty input".into())
+    }
+}
 
-    dbg!(result_a);
+impl Session for Repl {
+    fn help(&self) -> &str {
+        "step [n]         move n entries forward (default 1)\n\
+         show             decoder map of the current entry\n\
+         query <pattern>  digit for a pattern in the current entry\n\
+         eval             decoded output of the current entry"
+    }
+
+    fn eval(&mut self, command: &str, args: &[&str]) -> Result<String, Box<dyn Error>> {
+        match command {
+            "step" => {
+                let n = repl::arg_or(args, 0, 1)?;
+                self.current = self
+                    .current
+                    .saturating_add(n)
+                    .min(self.input.len().saturating_sub(1));
+                Ok(format!("entry {}/{}", self.current, self.input.len()))
+            }
+            "show" => {
+                let mut decoder = self.entry()?.decoder.iter().collect::<Vec<_>>();
+                decoder.sort_by_key(|(_, digit)| **digit);
+
+                let lines = decoder
+                    .iter()
+                    .map(|(pattern, digit)| format!("{:7} -> {}", pattern.to_string(), digit))
+                    .collect::<Vec<_>>();
+
+                Ok(lines.join("\n"))
+            }
+            "query" => {
+                let raw = args.first().ok_or("Missing pattern")?;
+                let pattern = Pattern::try_from(*raw)?;
+                let digit = self
+                    .entry()?
+                    .decoder
+                    .get(&pattern)
+                    .ok_or("Unknown pattern")?;
+                Ok(format!("{} -> {}", pattern, digit))
+            }
+            "eval" => {
+                let value = self.entry()?.decoded().ok_or("Can't decode output")?;
+                Ok(format!("{}", value))
+            }
+            _ => Err(format!("Unknown command: {}", command).into()),
+        }
+    }
+}
 
+pub(crate) fn solve(raw: &str) -> Result<(usize, usize), Box<dyn Error>> {
     let input = raw
         .lines()
         .map(Input::try_from)
         .collect::<Result<Vec<_>, _>>()?;
 
+    let result_a = raw
+        .lines()
+        .map(|l| l.split(" | ").nth(1).unwrap())
+        .flat_map(|l| l.split(" ").map(|p| p.len()))
+        .filter(|n| n == &2 || n == &4 || n == &3 || n == &7)
+        .count();
+
     let decoded = input
         .iter()
         .map(|l| l.decoded())
         .collect::<Option<Vec<_>>>()
-        .ok_or_else(|| "error")?;
+        .ok_or("error")?;
 
     let result_b: usize = decoded.iter().sum();
-    dbg!(result_b);
+
+    Ok((result_a, result_b))
+}
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let filename = std::env::args().nth(1).ok_or("Invalid input")?;
+    let raw = std::fs::read_to_string(filename)?;
+
+    if std::env::args().any(|a| a == "--repl") {
+        let input = raw
+            .lines()
+            .map(Input::try_from)
+            .collect::<Result<Vec<_>, _>>()?;
+        return repl::run(&mut Repl::new(input));
+    }
+
+    let (result_a, result_b) = solve(&raw)?;
+    println!("Task A: {}\nTask B: {}", result_a, result_b);
 
     Ok(())
 }
@@ -1,3 +1,4 @@
+use advent2021::check;
 use std::collections::{HashSet, VecDeque};
 use std::error::Error;
 
@@ -39,14 +40,12 @@ impl Game {
     }
 
     pub fn points<'a>(&'a self) -> impl Iterator<Item = Point> + 'a {
-        (0..self.ysize)
-            .map(move |y| {
-                (0..self.xsize).map(move |x| {
-                    let value = self.point(x, y);
-                    Point { x, y, value }
-                })
+        (0..self.ysize).flat_map(move |y| {
+            (0..self.xsize).map(move |x| {
+                let value = self.point(x, y);
+                Point { x, y, value }
             })
-            .flatten()
+        })
     }
 
     pub fn lowest<'a>(&'a self) -> impl Iterator<Item = Point> + 'a {
@@ -87,7 +86,7 @@ impl Game {
         let mut queue = VecDeque::new();
         queue.push_back((x, y));
         while let Some((x, y)) = queue.pop_front() {
-            if let None = visited.get(&(x, y)) {
+            if !visited.contains(&(x, y)) {
                 visited.insert((x, y));
 
                 self.around(x, y).for_each(|(x, y)| {
@@ -103,10 +102,8 @@ impl Game {
     }
 }
 
-fn main() -> Result<(), Box<dyn Error>> {
-    let filename = std::env::args().nth(1).ok_or("Invalid input")?;
-    let raw = std::fs::read_to_string(filename)?;
-    let game = Game::parse(&raw).ok_or("Parser error")?;
+pub(crate) fn solve(raw: &str) -> Result<(u32, usize), Box<dyn Error>> {
+    let game = Game::parse(raw).ok_or("Parser error")?;
     let result_a: u32 = game.lowest().map(|p| p.value + 1).sum();
 
     let mut areas = game
@@ -117,8 +114,20 @@ fn main() -> Result<(), Box<dyn Error>> {
     areas.sort();
     areas.reverse();
 
-    let result_b = areas[0..3].into_iter().fold(1, |a, e| a * e);
+    let result_b = areas[0..3].iter().product::<usize>();
+
+    Ok((result_a, result_b))
+}
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let filename = std::env::args().nth(1).ok_or("Invalid input")?;
+    let raw = std::fs::read_to_string(filename)?;
+
+    if std::env::args().any(|a| a == "--check") {
+        return check::report(&check::grid(&raw, |c| c.is_ascii_digit()));
+    }
 
+    let (result_a, result_b) = solve(&raw)?;
     println!("Task A: {}\nTask B: {}\n", result_a, result_b);
 
     Ok(())
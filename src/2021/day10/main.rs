@@ -1,3 +1,4 @@
+use advent2021::check::{self, Violation};
 use std::{collections::VecDeque, error::Error};
 
 enum Info {
@@ -51,9 +52,23 @@ fn calculate_missing(input: &[i64]) -> i64 {
     input.iter().fold(0, |a, e| a * 5 + e)
 }
 
-fn main() -> Result<(), Box<dyn Error>> {
-    let filename = std::env::args().nth(1).ok_or("Invalid input")?;
-    let raw = std::fs::read_to_string(filename)?;
+fn check(input: &str) -> Vec<Violation> {
+    input
+        .lines()
+        .enumerate()
+        .flat_map(|(idx, line)| {
+            line.chars()
+                .enumerate()
+                .filter(|(_, c)| !"[](){}<>".contains(*c))
+                .map(move |(col, c)| {
+                    let message = format!("unknown character {:?} at column {}", c, col + 1);
+                    Violation::new(idx + 1, message)
+                })
+        })
+        .collect()
+}
+
+pub(crate) fn solve(raw: &str) -> Result<(isize, i64), Box<dyn Error>> {
     let parsed = raw.lines().map(parse).collect::<Vec<_>>();
 
     let wrong = parsed
@@ -96,6 +111,18 @@ fn main() -> Result<(), Box<dyn Error>> {
     let idx = (missing.len() - 1) / 2;
     let middle = missing[idx];
 
+    Ok((result_a, middle))
+}
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let filename = std::env::args().nth(1).ok_or("Invalid input")?;
+    let raw = std::fs::read_to_string(filename)?;
+
+    if std::env::args().any(|a| a == "--check") {
+        return check::report(&check(&raw));
+    }
+
+    let (result_a, middle) = solve(&raw)?;
     println!("Task A: {}\nTask B: {}", result_a, middle);
 
     Ok(())
@@ -1,3 +1,4 @@
+use advent2021::check;
 use std::{
     collections::{HashSet, VecDeque},
     error::Error,
@@ -13,7 +14,7 @@ struct Grid {
 
 impl Grid {
     pub fn parse(input: &str) -> Result<Self, Box<dyn Error>> {
-        let xsize = input.lines().nth(0).ok_or_else(|| "Empty input")?.len();
+        let xsize = input.lines().nth(0).ok_or("Empty input")?.len();
         let storage: Vec<u32> = input.chars().filter_map(|c| c.to_digit(10)).collect();
         let ysize = storage.len() / xsize;
         Ok(Self {
@@ -59,17 +60,14 @@ impl Grid {
     fn points(&self) -> impl Iterator<Item = (i32, i32)> {
         let (xsize, ysize) = (self.xsize, self.ysize);
 
-        (0..ysize)
-            .map(move |y| (0..xsize).map(move |x| (x as i32, y as i32)))
-            .flatten()
+        (0..ysize).flat_map(move |y| (0..xsize).map(move |x| (x as i32, y as i32)))
     }
 
     fn around(&self, x: i32, y: i32) -> impl Iterator<Item = (i32, i32)> {
         let (xsize, ysize) = (self.xsize as i32, self.ysize as i32);
 
         (-1..=1)
-            .map(|dy| (-1..=1).map(move |dx| (dx, dy)))
-            .flatten()
+            .flat_map(|dy| (-1..=1).map(move |dx| (dx, dy)))
             .filter(|(dx, dy)| *dx != 0 || *dy != 0)
             .map(move |(dx, dy)| (x + dx, y + dy))
             .filter(move |(x, y)| *x >= 0 && *x < xsize && *y >= 0 && *y < ysize)
@@ -90,30 +88,39 @@ impl Grid {
 impl fmt::Display for Grid {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         for y in 0..self.ysize {
-            let idx = (y as usize) * self.xsize;
-            let xsize = self.xsize as usize;
+            let idx = y * self.xsize;
+            let xsize = self.xsize;
             let row = (idx..idx + xsize)
                 .map(|p| self.storage[p])
                 .map(|x| format!("{}", x))
                 .collect::<String>();
-            write!(f, "{}\n", row)?
+            writeln!(f, "{}", row)?
         }
 
         Ok(())
     }
 }
 
-fn main() -> Result<(), Box<dyn Error>> {
-    let filename = std::env::args().nth(1).ok_or("Invalid input")?;
-    let raw = std::fs::read_to_string(filename)?;
-    let mut grid = Grid::parse(&raw)?;
+pub(crate) fn solve(raw: &str) -> Result<(usize, usize), Box<dyn Error>> {
+    let mut grid = Grid::parse(raw)?;
 
     let result_a: usize = (0..100).map(|_| grid.flash()).sum();
     let result_b = (101..)
-        .skip_while(|_| grid.flash() != grid.size())
-        .nth(0)
+        .find(|_| grid.flash() == grid.size())
         .ok_or("Not found")?;
 
+    Ok((result_a, result_b))
+}
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let filename = std::env::args().nth(1).ok_or("Invalid input")?;
+    let raw = std::fs::read_to_string(filename)?;
+
+    if std::env::args().any(|a| a == "--check") {
+        return check::report(&check::grid(&raw, |c| c.is_ascii_digit()));
+    }
+
+    let (result_a, result_b) = solve(&raw)?;
     println!("Task A: {}, Task B: {}", result_a, result_b);
     Ok(())
 }
@@ -1,3 +1,4 @@
+use advent2021::repl::{self, Session};
 use std::{
     collections::{HashSet, VecDeque},
     error::Error,
@@ -7,7 +8,7 @@ use std::{
 fn is_upper(input: &str) -> bool {
     input
         .chars()
-        .nth(0)
+        .next()
         .map(|c| c.is_uppercase())
         .unwrap_or(false)
 }
@@ -88,11 +89,7 @@ impl Path {
     }
 
     pub fn completed(&self) -> bool {
-        if let Cave::End = self.last() {
-            true
-        } else {
-            false
-        }
+        matches!(self.last(), Cave::End)
     }
 
     pub fn allow(&self, added: &Cave, strict: bool) -> bool {
@@ -172,12 +169,14 @@ impl Game {
     }
 
     pub fn next_cave<'a>(&'a self, p: &'a Path) -> impl Iterator<Item = &'a Cave> + 'a {
-        let last = p.last();
+        self.neighbours(p.last())
+    }
 
+    pub fn neighbours<'a>(&'a self, cave: &'a Cave) -> impl Iterator<Item = &'a Cave> + 'a {
         self.paths.iter().filter_map(move |c| {
-            if c.0 == *last {
+            if c.0 == *cave {
                 Some(&c.1)
-            } else if c.1 == *last {
+            } else if c.1 == *cave {
                 Some(&c.0)
             } else {
                 None
@@ -185,14 +184,87 @@ impl Game {
         })
     }
 }
+
+struct Repl {
+    game: Game,
+    path: Path,
+}
+
+impl Repl {
+    pub fn new(game: Game) -> Self {
+        let path = Path::start();
+        Self { game, path }
+    }
+}
+
+impl Session for Repl {
+    fn help(&self) -> &str {
+        "step <cave>   extend the current path (part B rules)\n\
+         step          reset the current path to start\n\
+         show          current path and allowed next caves\n\
+         query <cave>  neighbours of a cave\n\
+         eval          number of paths for part A and B"
+    }
+
+    fn eval(&mut self, command: &str, args: &[&str]) -> Result<String, Box<dyn Error>> {
+        match command {
+            "step" => {
+                match args.first().map(|c| Cave::new(c)) {
+                    Some(cave) => {
+                        if !self.game.next_nodes(&self.path, false).contains(&cave) {
+                            return Err(format!("Can't move to {}", cave).into());
+                        }
+                        self.path = self.path.add(cave);
+                    }
+                    None => self.path = Path::start(),
+                }
+                Ok(format!("{}", self.path))
+            }
+            "show" => {
+                let next = self
+                    .game
+                    .next_nodes(&self.path, false)
+                    .iter()
+                    .map(|c| format!("{}", c))
+                    .collect::<Vec<_>>();
+
+                Ok(format!("{}\nnext: {}", self.path, next.join(", ")))
+            }
+            "query" => {
+                let cave = Cave::new(args.first().ok_or("Missing cave")?);
+                let neighbours = self
+                    .game
+                    .neighbours(&cave)
+                    .map(|c| format!("{}", c))
+                    .collect::<Vec<_>>();
+
+                Ok(neighbours.join(", "))
+            }
+            "eval" => {
+                let strict = self.game.all_paths(true).len();
+                let loose = self.game.all_paths(false).len();
+                Ok(format!("A: {}, B: {}", strict, loose))
+            }
+            _ => Err(format!("Unknown command: {}", command).into()),
+        }
+    }
+}
+
+pub(crate) fn solve(raw: &str) -> Result<(usize, usize), Box<dyn Error>> {
+    let game = Game::parse(raw)?;
+
+    Ok((game.all_paths(true).len(), game.all_paths(false).len()))
+}
+
 fn main() -> Result<(), Box<dyn Error>> {
     let filename = std::env::args().nth(1).ok_or("Invalid input")?;
     let raw = std::fs::read_to_string(filename)?;
-    let game = Game::parse(&raw)?;
 
-    let result_a = game.all_paths(true).len();
-    let result_b = game.all_paths(false).len();
+    if std::env::args().any(|a| a == "--repl") {
+        return repl::run(&mut Repl::new(Game::parse(&raw)?));
+    }
 
+    let (result_a, result_b) = solve(&raw)?;
     println!("Task A: {}\nTask B: {}", result_a, result_b);
     Ok(())
 }
@@ -3,7 +3,7 @@ use regex::Regex;
 use std::{collections::HashSet, error::Error, fmt};
 
 #[derive(Debug)]
-struct Field {
+pub(crate) struct Field {
     dots: HashSet<(i32, i32)>,
 }
 
@@ -29,13 +29,13 @@ impl Field {
             .dots
             .iter()
             .copied()
-            .filter_map(|(x, y)| match rule {
-                &Rule::Horizontal(line) => match y {
+            .filter_map(|(x, y)| match *rule {
+                Rule::Horizontal(line) => match y {
                     y if y < line => Some((x, y)),
                     y if y > line => Some((x, 2 * line - y)),
                     _ => None,
                 },
-                &Rule::Vertical(line) => match x {
+                Rule::Vertical(line) => match x {
                     x if x < line => Some((x, y)),
                     x if x > line => Some((2 * line - x, y)),
                     _ => None,
@@ -68,7 +68,7 @@ impl fmt::Display for Field {
                 }
             }
 
-            write!(f, "\n")?;
+            writeln!(f)?;
         }
 
         Ok(())
@@ -76,7 +76,7 @@ impl fmt::Display for Field {
 }
 
 #[derive(Debug)]
-enum Rule {
+pub(crate) enum Rule {
     Horizontal(i32),
     Vertical(i32),
 }
@@ -101,21 +101,31 @@ impl Rule {
     }
 }
 
-fn main() -> Result<(), Box<dyn Error>> {
-    let filename = std::env::args().nth(1).ok_or("Invalid input")?;
-    let raw = std::fs::read_to_string(filename)?;
-
-    let field = raw.split("\n\n").nth(0).and_then(Field::parse).unwrap();
+pub(crate) fn solve(raw: &str) -> Result<(usize, Field), Box<dyn Error>> {
+    let field = raw
+        .split("\n\n")
+        .next()
+        .and_then(Field::parse)
+        .ok_or("Can't parse field")?;
     let rules = raw
         .split("\n\n")
         .nth(1)
         .and_then(|raw| raw.lines().map(Rule::parse).collect::<Option<Vec<_>>>())
-        .unwrap();
+        .filter(|rules| !rules.is_empty())
+        .ok_or("Can't parse rules")?;
 
     let field = field.fold(&rules[0]);
     let result_a = field.size();
     let result_b = rules[1..].iter().fold(field, |f, r| f.fold(r));
 
+    Ok((result_a, result_b))
+}
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let filename = std::env::args().nth(1).ok_or("Invalid input")?;
+    let raw = std::fs::read_to_string(filename)?;
+
+    let (result_a, result_b) = solve(&raw)?;
     println!("Task A: {}\nTask B:\n{}", result_a, result_b);
 
     Ok(())
@@ -145,24 +145,29 @@ impl Game {
     }
 }
 
-fn main() -> Result<(), Box<dyn Error>> {
-    let filename = std::env::args().nth(1).ok_or("Invalid input")?;
-    let raw = std::fs::read_to_string(filename)?;
-
-    let mut game = Game::parse(&raw).expect("Invalid input");
+pub(crate) fn solve(raw: &str) -> Result<(u64, u64), Box<dyn Error>> {
+    let mut game = Game::parse(raw).ok_or("Invalid input")?;
 
     for _ in 0..10 {
         game.step()
     }
 
-    let result_a = game.result().expect("Result A not found");
+    let result_a = game.result().ok_or("Result A not found")?;
 
     for _ in 10..40 {
         game.step()
     }
 
-    let result_b = game.result().expect("Result B not found");
+    let result_b = game.result().ok_or("Result B not found")?;
+
+    Ok((result_a, result_b))
+}
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let filename = std::env::args().nth(1).ok_or("Invalid input")?;
+    let raw = std::fs::read_to_string(filename)?;
 
+    let (result_a, result_b) = solve(&raw)?;
     println!("Task A: {}\nTask B: {}", result_a, result_b);
 
     Ok(())
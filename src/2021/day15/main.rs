@@ -1,3 +1,4 @@
+use advent2021::check;
 use std::{collections::HashMap, error::Error, fmt};
 
 #[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
@@ -78,7 +79,7 @@ impl fmt::Display for Grid {
             for x in 0..self.xsize {
                 write!(f, "{}", self.get(Point::new(x as i32, y as i32)).unwrap().1)?
             }
-            write!(f, "\n")?
+            writeln!(f)?
         }
 
         Ok(())
@@ -175,7 +176,7 @@ where
 
     open.insert(from, Path::new(from, 0, from.distanct(to)));
 
-    while open.len() > 0 {
+    while !open.is_empty() {
         // println!("O: {}, C: {}", open.len(), closed.len());
         // println!("Open:");
         // for (k, v) in open.iter() {
@@ -198,7 +199,7 @@ where
             )
             .expect("min not found");
 
-        let point = point.clone();
+        let point = *point;
         let path = path.clone();
 
         // println!("Selected: {} -> {}", point, path);
@@ -240,10 +241,8 @@ where
     panic!("NO FOUN");
 }
 
-fn main() -> Result<(), Box<dyn Error>> {
-    let filename = std::env::args().nth(1).ok_or("Invalid input")?;
-    let raw = std::fs::read_to_string(filename)?;
-    let grid = Grid::parse(&raw).expect("Can't parse grid");
+pub(crate) fn solve(raw: &str) -> Result<(i32, i32), Box<dyn Error>> {
+    let grid = Grid::parse(raw).ok_or("Can't parse grid")?;
     let target = grid.target();
     let result_a = astar(&grid, Point::new(0, 0), target);
 
@@ -253,7 +252,19 @@ fn main() -> Result<(), Box<dyn Error>> {
     // dbg!(large_target);
     let result_b = astar(&large, Point::new(0, 0), large_target);
 
-    println!("Task A: {}\nTask B: {}", result_a.f, result_b.f);
+    Ok((result_a.f, result_b.f))
+}
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let filename = std::env::args().nth(1).ok_or("Invalid input")?;
+    let raw = std::fs::read_to_string(filename)?;
+
+    if std::env::args().any(|a| a == "--check") {
+        return check::report(&check::grid(&raw, |c| c.is_ascii_digit()));
+    }
+
+    let (result_a, result_b) = solve(&raw)?;
+    println!("Task A: {}\nTask B: {}", result_a, result_b);
 
     Ok(())
 }
@@ -21,7 +21,7 @@ impl RawData {
     pub fn parse(input: &str) -> Option<Self> {
         let data = input
             .chars()
-            .filter(|c| c.is_digit(16))
+            .filter(|c| c.is_ascii_hexdigit())
             .map(|c| c.to_digit(16).map(|d| format!("{:04b}", d)))
             .collect::<Option<Vec<_>>>()?
             .join("");
@@ -87,8 +87,8 @@ impl Payload {
     fn value(&self) -> u64 {
         match self {
             Self::Literal(n) => *n,
-            Self::Operator(p, 0) => p.iter().map(|p| p.value()).fold(0, |a, e| a + e),
-            Self::Operator(p, 1) => p.iter().map(|p| p.value()).fold(1, |a, e| a * e),
+            Self::Operator(p, 0) => p.iter().map(|p| p.value()).sum(),
+            Self::Operator(p, 1) => p.iter().map(|p| p.value()).product(),
             Self::Operator(p, 2) => p.iter().map(|p| p.value()).min().expect("Empty min"),
             Self::Operator(p, 3) => p.iter().map(|p| p.value()).max().expect("Empty max"),
             Self::Operator(p, 5) => bool_to_value(p[0].value() > p[1].value()),
@@ -170,15 +170,19 @@ impl Packet {
     }
 }
 
+pub(crate) fn solve(input: &str) -> Result<(usize, u64), Box<dyn Error>> {
+    let mut raw = RawData::parse(input).ok_or("Can't parse input")?;
+
+    let (packet, _) = Packet::parse(&mut raw).ok_or("Can't parse packet")?;
+
+    Ok((packet.version_sum(), packet.value()))
+}
+
 fn main() -> Result<(), Box<dyn Error>> {
     let filename = std::env::args().nth(1).ok_or("Invalid input")?;
     let input = std::fs::read_to_string(filename)?;
-    let mut raw = RawData::parse(&input).ok_or_else(|| "Can't parse input")?;
-
-    let (packet, _) = Packet::parse(&mut raw).ok_or_else(|| "Can't parse packet")?;
-    let result_a = packet.version_sum();
-    let result_b = packet.value();
 
+    let (result_a, result_b) = solve(&input)?;
     println!("Task A: {}\nTask B: {}", result_a, result_b);
     Ok(())
 }
@@ -190,7 +194,7 @@ mod tests {
     #[test]
     fn test_literal() {
         let input = "D2FE28";
-        let mut raw = RawData::parse(&input).unwrap();
+        let mut raw = RawData::parse(input).unwrap();
         let (packet, size) = Packet::parse(&mut raw).unwrap();
         let payload = Payload::literal(2021);
         let expected = Packet::new(6, payload);
@@ -202,7 +206,7 @@ mod tests {
     #[test]
     fn test_operator_1() {
         let input = "38006F45291200";
-        let mut raw = RawData::parse(&input).unwrap();
+        let mut raw = RawData::parse(input).unwrap();
         let (packet, size) = Packet::parse(&mut raw).unwrap();
         let a = Packet::new(6, Payload::literal(10));
         let b = Packet::new(2, Payload::literal(20));
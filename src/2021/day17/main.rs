@@ -46,11 +46,7 @@ impl Target {
             ymax,
         } = self;
 
-        if x >= xmin && x <= xmax && y >= ymin && y <= ymax {
-            true
-        } else {
-            false
-        }
+        x >= xmin && x <= xmax && y >= ymin && y <= ymax
     }
 }
 
@@ -97,25 +93,26 @@ impl Game {
         let y_limit = self.target.ymin.abs();
         (-y_limit..=y_limit)
             .rev()
-            .map(move |dy| (0..=x_limit).map(move |dx| (dx, dy)))
-            .flatten()
-            .filter_map(move |(dx, dy)| match self.hit(dx, dy) {
-                Some(height) => Some(height),
-                None => None,
-            })
+            .flat_map(move |dy| (0..=x_limit).map(move |dx| (dx, dy)))
+            .filter_map(move |(dx, dy)| self.hit(dx, dy))
     }
 }
 
-fn main() -> Result<(), Box<dyn Error>> {
-    let filename = std::env::args().nth(1).ok_or("Invalid input")?;
-    let input = std::fs::read_to_string(filename)?;
-
-    let game = Game::parse(&input).ok_or("Can't parse input")?;
+pub(crate) fn solve(input: &str) -> Result<(i32, usize), Box<dyn Error>> {
+    let game = Game::parse(input).ok_or("Can't parse input")?;
     let mut results = game.results();
 
     let result_a = results.next().ok_or("Can't find result A")?;
     let result_b = results.count() + 1;
 
+    Ok((result_a, result_b))
+}
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let filename = std::env::args().nth(1).ok_or("Invalid input")?;
+    let input = std::fs::read_to_string(filename)?;
+
+    let (result_a, result_b) = solve(&input)?;
     println!("Task A: {}\nTask B: {}", result_a, result_b);
 
     Ok(())
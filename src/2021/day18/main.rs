@@ -1,3 +1,4 @@
+use advent2021::repl::{self, Session};
 use std::{error::Error, fmt};
 
 #[derive(Debug, PartialEq)]
@@ -38,9 +39,9 @@ impl<'a> Iterator for Tokenizer<'a> {
                     self.position += c.len_utf8();
                     return Some(Token::Comma);
                 }
-                c if c.is_digit(10) => {
+                c if c.is_ascii_digit() => {
                     let next_chars_len: usize = chars
-                        .take_while(|c| c.is_digit(10))
+                        .take_while(|c| c.is_ascii_digit())
                         .map(|c| c.len_utf8())
                         .sum();
                     let len = next_chars_len + c.len_utf8();
@@ -220,7 +221,7 @@ impl Number {
         }
     }
 
-    fn find_explode(&mut self, depth: usize) -> Option<ExplodePosition> {
+    fn find_explode(&mut self, depth: usize) -> Option<ExplodePosition<'_>> {
         if depth > 0 {
             match self {
                 Number::Single(_) => None,
@@ -294,15 +295,85 @@ impl fmt::Display for Number {
     }
 }
 
-fn main() -> Result<(), Box<dyn Error>> {
-    let filename = std::env::args().nth(1).ok_or("Invalid input")?;
-    let input = std::fs::read_to_string(filename)?;
+struct Repl {
+    numbers: Vec<Number>,
+    sum: Option<Number>,
+    added: usize,
+}
+
+impl Repl {
+    pub fn new(numbers: Vec<Number>) -> Self {
+        Self {
+            numbers,
+            sum: None,
+            added: 0,
+        }
+    }
+
+    fn describe(&self) -> String {
+        match &self.sum {
+            Some(sum) => format!(
+                "{}/{}: {} (magnitude {})",
+                self.added,
+                self.numbers.len(),
+                sum,
+                sum.magnitude()
+            ),
+            None => format!("0/{}: empty", self.numbers.len()),
+        }
+    }
+}
+
+impl Session for Repl {
+    fn help(&self) -> &str {
+        "step [n]      add the next n numbers to the partial sum (default 1)\n\
+         show          partial sum and its magnitude\n\
+         query <i>     number at line i (0-based)\n\
+         eval <num>    reduce a snailfish number and print its magnitude"
+    }
+
+    fn eval(&mut self, command: &str, args: &[&str]) -> Result<String, Box<dyn Error>> {
+        match command {
+            "step" => {
+                let n: usize = repl::arg_or(args, 0, 1)?;
+                for number in self.numbers.iter().skip(self.added).take(n) {
+                    self.sum = match self.sum.take() {
+                        Some(sum) => Some(sum.add(number.clone())),
+                        None => Some(number.clone()),
+                    };
+                    self.added += 1;
+                }
+                Ok(self.describe())
+            }
+            "show" => Ok(self.describe()),
+            "query" => {
+                let idx: usize = repl::arg(args, 0)?;
+                let number = self.numbers.get(idx).ok_or("No such number")?;
+                Ok(format!("{} (magnitude {})", number, number.magnitude()))
+            }
+            "eval" => {
+                let mut number = Number::parse(&args.concat()).ok_or("Can't parse number")?;
+                number.reduce();
+                Ok(format!("{} (magnitude {})", number, number.magnitude()))
+            }
+            _ => Err(format!("Unknown command: {}", command).into()),
+        }
+    }
+}
+
+fn parse(input: &str) -> Result<Vec<Number>, Box<dyn Error>> {
     let numbers = input
         .lines()
-        .map(|line| Number::parse(line))
+        .map(Number::parse)
         .collect::<Option<Vec<_>>>()
         .ok_or("Can't parse input")?;
 
+    Ok(numbers)
+}
+
+pub(crate) fn solve(input: &str) -> Result<(i32, i32), Box<dyn Error>> {
+    let numbers = parse(input)?;
+
     let sum = numbers
         .iter()
         .cloned()
@@ -327,7 +398,19 @@ fn main() -> Result<(), Box<dyn Error>> {
         }
     }
 
-    println!("Task A: {}, Task B: {}", result_a, max_sum.unwrap());
+    Ok((result_a, max_sum.ok_or("Need two numbers")?))
+}
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let filename = std::env::args().nth(1).ok_or("Invalid input")?;
+    let input = std::fs::read_to_string(filename)?;
+
+    if std::env::args().any(|a| a == "--repl") {
+        return repl::run(&mut Repl::new(parse(&input)?));
+    }
+
+    let (result_a, result_b) = solve(&input)?;
+    println!("Task A: {}, Task B: {}", result_a, result_b);
 
     Ok(())
 }
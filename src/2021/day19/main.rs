@@ -0,0 +1,246 @@
+use advent2021::geometry::{Point3, Rotation};
+use std::{
+    collections::{HashMap, HashSet, VecDeque},
+    error::Error,
+};
+
+const OVERLAP: usize = 12;
+
+#[derive(Debug)]
+struct Scanner {
+    id: usize,
+    beacons: Vec<Point3>,
+    fingerprint: HashMap<i64, usize>,
+}
+
+impl Scanner {
+    pub fn parse(input: &str) -> Option<Self> {
+        let mut lines = input.lines();
+        let id = lines
+            .next()?
+            .trim_start_matches("--- scanner ")
+            .trim_end_matches(" ---")
+            .parse::<usize>()
+            .ok()?;
+
+        let beacons = lines
+            .map(|line| {
+                let coords = line
+                    .split(',')
+                    .map(|n| n.parse::<i64>().ok())
+                    .collect::<Option<Vec<_>>>()?;
+
+                match *coords.as_slice() {
+                    [x, y, z] => Some(Point3::new(x, y, z)),
+                    _ => None,
+                }
+            })
+            .collect::<Option<Vec<_>>>()?;
+
+        Some(Self::new(id, beacons))
+    }
+
+    pub fn new(id: usize, beacons: Vec<Point3>) -> Self {
+        let mut fingerprint = HashMap::new();
+
+        for (idx, a) in beacons.iter().enumerate() {
+            for b in beacons[idx + 1..].iter() {
+                *fingerprint.entry((*a - *b).norm()).or_insert(0) += 1;
+            }
+        }
+
+        Self {
+            id,
+            beacons,
+            fingerprint,
+        }
+    }
+
+    // Distances between beacons don't depend on the orientation, so two
+    // scanners sharing 12 beacons must share at least 66 pairwise distances.
+    pub fn may_overlap(&self, other: &Scanner) -> bool {
+        let shared: usize = self
+            .fingerprint
+            .iter()
+            .filter_map(|(d, count)| other.fingerprint.get(d).map(|c| *c.min(count)))
+            .sum();
+
+        shared >= OVERLAP * (OVERLAP - 1) / 2
+    }
+
+    // Tries every orientation and looks for an offset which maps at least 12
+    // beacons onto the known ones.
+    pub fn align(&self, known: &[Point3]) -> Option<Located> {
+        for rotation in Rotation::all() {
+            let rotated = self
+                .beacons
+                .iter()
+                .map(|b| rotation.apply(*b))
+                .collect::<Vec<_>>();
+
+            let mut offsets = HashMap::new();
+
+            for k in known {
+                for b in rotated.iter() {
+                    let offset = *k - *b;
+                    let count = offsets.entry(offset).or_insert(0);
+                    *count += 1;
+
+                    if *count >= OVERLAP {
+                        let beacons = rotated.iter().map(|b| *b + offset).collect();
+                        return Some(Located::new(offset, beacons));
+                    }
+                }
+            }
+        }
+
+        None
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Located {
+    position: Point3,
+    beacons: Vec<Point3>,
+}
+
+impl Located {
+    pub fn new(position: Point3, beacons: Vec<Point3>) -> Self {
+        Self { position, beacons }
+    }
+}
+
+fn locate(scanners: &[Scanner]) -> Option<Vec<Located>> {
+    let first = scanners.first()?;
+    let mut located: Vec<Option<Located>> = vec![None; scanners.len()];
+    located[0] = Some(Located::new(Point3::new(0, 0, 0), first.beacons.clone()));
+
+    let mut queue = VecDeque::new();
+    queue.push_back(0);
+
+    while let Some(idx) = queue.pop_front() {
+        let known = located[idx].clone()?;
+
+        for (next, scanner) in scanners.iter().enumerate() {
+            if located[next].is_some() || !scanners[idx].may_overlap(scanner) {
+                continue;
+            }
+
+            if let Some(found) = scanner.align(&known.beacons) {
+                located[next] = Some(found);
+                queue.push_back(next);
+            }
+        }
+    }
+
+    for (scanner, found) in scanners.iter().zip(located.iter()) {
+        if found.is_none() {
+            eprintln!("scanner {} can't be aligned", scanner.id);
+        }
+    }
+
+    located.into_iter().collect()
+}
+
+pub(crate) fn solve(input: &str) -> Result<(usize, i64), Box<dyn Error>> {
+    let scanners = input
+        .trim_end()
+        .split("\n\n")
+        .map(Scanner::parse)
+        .collect::<Option<Vec<_>>>()
+        .ok_or("Can't parse input")?;
+
+    let located = locate(&scanners).ok_or("Can't align scanners")?;
+
+    let beacons = located
+        .iter()
+        .flat_map(|l| l.beacons.iter())
+        .collect::<HashSet<_>>();
+
+    let result_a = beacons.len();
+    let result_b = located
+        .iter()
+        .flat_map(|a| {
+            located
+                .iter()
+                .map(move |b| a.position.manhattan(b.position))
+        })
+        .max()
+        .unwrap_or(0);
+
+    Ok((result_a, result_b))
+}
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let filename = std::env::args().nth(1).ok_or("Invalid input")?;
+    let input = std::fs::read_to_string(filename)?;
+
+    let (result_a, result_b) = solve(&input)?;
+    println!("Task A: {}\nTask B: {}", result_a, result_b);
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn beacons() -> Vec<Point3> {
+        (0..15)
+            .map(|i| Point3::new(i * 37 % 101, i * i % 89 - 40, (i * 53) % 97 - i))
+            .collect()
+    }
+
+    #[test]
+    fn test_parse() {
+        let input = "--- scanner 3 ---\n404,-588,-901\n-1,2,-3";
+        let scanner = Scanner::parse(input).unwrap();
+
+        assert_eq!(scanner.id, 3);
+        assert_eq!(
+            scanner.beacons,
+            vec![Point3::new(404, -588, -901), Point3::new(-1, 2, -3)]
+        );
+        assert_eq!(
+            scanner
+                .fingerprint
+                .get(&(405 * 405 + 590 * 590 + 898 * 898)),
+            Some(&1)
+        );
+    }
+
+    #[test]
+    fn test_align() {
+        let absolute = beacons();
+        let position = Point3::new(68, -1246, -43);
+        let rotation = Rotation::all()[17];
+        let seen = absolute
+            .iter()
+            .map(|b| rotation.apply(*b - position))
+            .collect::<Vec<_>>();
+
+        let a = Scanner::new(0, absolute.clone());
+        let b = Scanner::new(1, seen);
+        assert!(a.may_overlap(&b));
+
+        let located = b.align(&absolute).unwrap();
+        assert_eq!(located.position, position);
+        assert_eq!(located.beacons, absolute);
+    }
+
+    #[test]
+    fn test_locate() {
+        let absolute = beacons();
+        let rotation = Rotation::all()[5];
+        let position = Point3::new(-20, 1133, -1061);
+        let seen = absolute
+            .iter()
+            .map(|b| rotation.apply(*b - position))
+            .collect::<Vec<_>>();
+
+        let scanners = vec![Scanner::new(0, absolute), Scanner::new(1, seen)];
+        let located = locate(&scanners).unwrap();
+
+        assert_eq!(located[1].position, position);
+    }
+}
@@ -31,6 +31,7 @@ struct BoundingBox {
 }
 
 impl BoundingBox {
+    #[cfg(test)]
     fn new(xmin: i32, xmax: i32, ymin: i32, ymax: i32) -> Self {
         Self {
             xmin,
@@ -49,7 +50,7 @@ impl BoundingBox {
             }
         }
 
-        return result;
+        result
     }
 
     fn from_point(x: i32, y: i32) -> Self {
@@ -104,8 +105,7 @@ impl Field {
 
     fn rule_index(&self, x: i32, y: i32) -> usize {
         (y - 1..=y + 1)
-            .map(|y| (x - 1..=x + 1).map(move |x| if self.get(x, y) { 1 } else { 0 }))
-            .flatten()
+            .flat_map(|y| (x - 1..=x + 1).map(move |x| if self.get(x, y) { 1 } else { 0 }))
             .enumerate()
             .map(|(idx, v)| v << (8 - idx))
             .sum()
@@ -167,16 +167,14 @@ impl std::fmt::Display for Field {
                     }
                 }
 
-                write!(f, "\n")?
+                writeln!(f)?
             }
         }
         Ok(())
     }
 }
 
-fn main() -> Result<(), Box<dyn Error>> {
-    let filename = std::env::args().nth(1).ok_or("Missing input filename")?;
-    let data = std::fs::read_to_string(filename)?;
+pub(crate) fn solve(data: &str) -> Result<(usize, usize), Box<dyn Error>> {
     let mut raw = data.split("\n\n");
 
     let rules = raw.next().map(Rules::new).ok_or("Invalid input")?;
@@ -188,6 +186,14 @@ fn main() -> Result<(), Box<dyn Error>> {
     (0..48).for_each(|_| field = field.step(&rules));
     let pixels_b = field.pixels();
 
+    Ok((pixels_a, pixels_b))
+}
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let filename = std::env::args().nth(1).ok_or("Missing input filename")?;
+    let data = std::fs::read_to_string(filename)?;
+
+    let (pixels_a, pixels_b) = solve(&data)?;
     println!("Result A: {}\nResult B: {}", pixels_a, pixels_b);
     Ok(())
 }
@@ -199,18 +205,18 @@ mod test {
     #[test]
     fn test_rule() {
         let input = "..##..#";
-        let rule = Rules::new(&input);
-        assert_eq!(true, rule.get(2));
-        assert_eq!(false, rule.get(4));
-        assert_eq!(false, rule.get(4));
-        assert_eq!(true, rule.get(6));
-        assert_eq!(false, rule.get(979));
+        let rule = Rules::new(input);
+        assert!(rule.get(2));
+        assert!(!rule.get(4));
+        assert!(!rule.get(4));
+        assert!(rule.get(6));
+        assert!(!rule.get(979));
     }
 
     #[test]
     fn test_bounding_box() {
         let input = "#.#\n...\n#..";
-        let field = Field::new(&input);
+        let field = Field::new(input);
         let expected = Some(BoundingBox::new(0, 2, 0, 2));
         assert_eq!(expected, field.bb);
     }
@@ -218,7 +224,7 @@ mod test {
     #[test]
     fn text_rule_index() {
         let input = "#..\n...\n.#.";
-        let field = Field::new(&input);
+        let field = Field::new(input);
         assert_eq!(0b000010000, field.rule_index(0, 0));
         assert_eq!(0b000000001, field.rule_index(-1, -1));
         assert_eq!(0b100000010, field.rule_index(1, 1));
@@ -15,7 +15,7 @@ fn parse_input(input: &str) -> Option<(usize, usize)> {
         })
         .collect::<Option<Vec<_>>>()?;
 
-    let a = numbers.get(0).cloned()?;
+    let a = numbers.first().cloned()?;
     let b = numbers.get(1).cloned()?;
 
     Some((a, b))
@@ -228,10 +228,7 @@ impl State {
     }
 
     fn finished(&self) -> bool {
-        match self.result {
-            GameResult::InProgress => false,
-            _ => true,
-        }
+        !matches!(self.result, GameResult::InProgress)
     }
 }
 
@@ -315,17 +312,15 @@ impl QuantumGame {
 impl Display for QuantumGame {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         for (state, count) in self.world.iter() {
-            write!(f, "{:05} - {}\n", count, state)?;
+            writeln!(f, "{:05} - {}", count, state)?;
         }
 
         Ok(())
     }
 }
 
-fn main() -> Result<(), Box<dyn Error>> {
-    let filename = std::env::args().nth(1).ok_or("Invalid input")?;
-    let input = std::fs::read_to_string(filename)?;
-    let (a, b) = parse_input(&input).ok_or("Can't parse input")?;
+pub(crate) fn solve(input: &str) -> Result<(usize, usize), Box<dyn Error>> {
+    let (a, b) = parse_input(input).ok_or("Can't parse input")?;
 
     let mut game = Game::new(a, b);
     game.round();
@@ -335,6 +330,14 @@ fn main() -> Result<(), Box<dyn Error>> {
     quantum_game.run();
     let result_b = quantum_game.result_b();
 
+    Ok((result_a, result_b))
+}
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let filename = std::env::args().nth(1).ok_or("Invalid input")?;
+    let input = std::fs::read_to_string(filename)?;
+
+    let (result_a, result_b) = solve(&input)?;
     println!("Task A: {}, Task B: {}", result_a, result_b);
 
     Ok(())
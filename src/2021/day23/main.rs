@@ -0,0 +1,339 @@
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, HashMap},
+    error::Error,
+    fmt,
+};
+
+const HALLWAY: usize = 11;
+const ROOMS: usize = 4;
+const DOORS: [usize; ROOMS] = [2, 4, 6, 8];
+const ENERGY: [usize; ROOMS] = [1, 10, 100, 1000];
+const UNFOLDED: [&str; 2] = ["  #D#C#B#A#", "  #D#B#A#C#"];
+
+// Every cell takes 3 bits: 0 is empty, 1..=4 are amphipods A..D. Hallway
+// cells come first, then the rooms from left to right, top slot first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Ord, PartialOrd)]
+struct State(u128);
+
+impl State {
+    fn get(&self, idx: usize) -> Option<usize> {
+        match (self.0 >> (idx * 3)) & 0b111 {
+            0 => None,
+            n => Some(n as usize - 1),
+        }
+    }
+
+    fn set(&self, idx: usize, value: Option<usize>) -> Self {
+        let cleared = self.0 & !(0b111 << (idx * 3));
+        let value = value.map(|v| v as u128 + 1).unwrap_or(0);
+        Self(cleared | (value << (idx * 3)))
+    }
+
+    fn moved(&self, from: usize, to: usize) -> Self {
+        self.set(to, self.get(from)).set(from, None)
+    }
+}
+
+#[derive(Debug)]
+struct Burrow {
+    depth: usize,
+    start: State,
+}
+
+impl Burrow {
+    pub fn parse(input: &str) -> Option<Self> {
+        let rows = input
+            .lines()
+            .map(|line| {
+                line.chars()
+                    .filter(|c| ('A'..='D').contains(c))
+                    .map(|c| c as usize - 'A' as usize)
+                    .collect::<Vec<_>>()
+            })
+            .filter(|row| !row.is_empty())
+            .collect::<Vec<_>>();
+
+        // every cell has to fit into the 128 bits of a state
+        let depth = rows.len();
+        if depth == 0 || (HALLWAY + ROOMS * depth) * 3 > 128 {
+            return None;
+        }
+        if rows.iter().any(|row| row.len() != ROOMS) {
+            return None;
+        }
+
+        let mut start = State(0);
+        for (slot, row) in rows.iter().enumerate() {
+            for (room, kind) in row.iter().enumerate() {
+                start = start.set(HALLWAY + room * depth + slot, Some(*kind));
+            }
+        }
+
+        Some(Self { depth, start })
+    }
+
+    // Inserts the two folded lines of part B between the first and the
+    // last row of the rooms.
+    pub fn unfold(input: &str) -> Option<Self> {
+        let mut lines = input.lines().collect::<Vec<_>>();
+        let first = lines
+            .iter()
+            .position(|l| l.contains(|c| ('A'..='D').contains(&c)))?;
+        lines.splice(first + 1..first + 1, UNFOLDED.iter().copied());
+
+        Self::parse(&lines.join("\n"))
+    }
+
+    fn slot(&self, room: usize, slot: usize) -> usize {
+        HALLWAY + room * self.depth + slot
+    }
+
+    fn goal(&self) -> State {
+        (0..ROOMS).fold(State(0), |state, room| {
+            (0..self.depth).fold(state, |state, slot| {
+                state.set(self.slot(room, slot), Some(room))
+            })
+        })
+    }
+
+    // A room accepts amphipods when it holds only amphipods of its own kind.
+    fn settled(&self, state: &State, room: usize) -> bool {
+        (0..self.depth).all(|slot| match state.get(self.slot(room, slot)) {
+            Some(kind) => kind == room,
+            None => true,
+        })
+    }
+
+    fn hallway_clear(&self, state: &State, from: usize, to: usize) -> bool {
+        let (a, b) = if from < to {
+            (from + 1, to)
+        } else {
+            (to, from - 1)
+        };
+        (a..=b).all(|idx| state.get(idx).is_none())
+    }
+
+    pub fn moves(&self, state: &State) -> Vec<(State, usize)> {
+        let mut result = vec![];
+
+        for (room, door) in DOORS.iter().enumerate() {
+            if self.settled(state, room) {
+                continue;
+            }
+
+            let top = (0..self.depth).find(|slot| state.get(self.slot(room, *slot)).is_some());
+
+            if let Some(slot) = top {
+                let from = self.slot(room, slot);
+                let kind = state.get(from).unwrap_or(0);
+
+                for target in (0..HALLWAY).filter(|h| !DOORS.contains(h)) {
+                    if self.hallway_clear(state, *door, target) && state.get(target).is_none() {
+                        let steps = slot + 1 + (*door as isize - target as isize).unsigned_abs();
+                        result.push((state.moved(from, target), steps * ENERGY[kind]));
+                    }
+                }
+            }
+        }
+
+        for from in 0..HALLWAY {
+            if let Some(kind) = state.get(from) {
+                if !self.settled(state, kind) || !self.hallway_clear(state, from, DOORS[kind]) {
+                    continue;
+                }
+
+                let slot = (0..self.depth)
+                    .rev()
+                    .find(|slot| state.get(self.slot(kind, *slot)).is_none());
+
+                if let Some(slot) = slot {
+                    let steps = slot + 1 + (DOORS[kind] as isize - from as isize).unsigned_abs();
+                    let to = self.slot(kind, slot);
+                    result.push((state.moved(from, to), steps * ENERGY[kind]));
+                }
+            }
+        }
+
+        result
+    }
+
+    pub fn organize(&self) -> Option<Solution> {
+        let goal = self.goal();
+        let mut energy: HashMap<State, usize> = HashMap::new();
+        let mut previous: HashMap<State, State> = HashMap::new();
+        let mut queue = BinaryHeap::new();
+
+        energy.insert(self.start, 0);
+        queue.push(Reverse((0, self.start)));
+
+        while let Some(Reverse((cost, state))) = queue.pop() {
+            if state == goal {
+                let mut path = vec![state];
+                while let Some(prev) = previous.get(path.last()?) {
+                    path.push(*prev);
+                }
+                path.reverse();
+
+                return Some(Solution { energy: cost, path });
+            }
+
+            if energy.get(&state).is_some_and(|e| *e < cost) {
+                continue;
+            }
+
+            for (next, step) in self.moves(&state) {
+                let next_cost = cost + step;
+                if energy.get(&next).is_none_or(|e| next_cost < *e) {
+                    energy.insert(next, next_cost);
+                    previous.insert(next, state);
+                    queue.push(Reverse((next_cost, next)));
+                }
+            }
+        }
+
+        None
+    }
+
+    pub fn frame(&self, state: &State) -> Frame<'_> {
+        Frame {
+            burrow: self,
+            state: *state,
+        }
+    }
+}
+
+struct Solution {
+    energy: usize,
+    path: Vec<State>,
+}
+
+struct Frame<'a> {
+    burrow: &'a Burrow,
+    state: State,
+}
+
+impl fmt::Display for Frame<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let cell = |idx| match self.state.get(idx) {
+            Some(kind) => (b'A' + kind as u8) as char,
+            None => '.',
+        };
+
+        writeln!(f, "#############")?;
+        let hallway = (0..HALLWAY).map(cell).collect::<String>();
+        writeln!(f, "#{}#", hallway)?;
+
+        for slot in 0..self.burrow.depth {
+            let (left, right) = if slot == 0 {
+                ("###", "###")
+            } else {
+                ("  #", "#")
+            };
+            let rooms = (0..ROOMS)
+                .map(|room| cell(self.burrow.slot(room, slot)).to_string())
+                .collect::<Vec<_>>()
+                .join("#");
+            writeln!(f, "{}{}{}", left, rooms, right)?;
+        }
+
+        writeln!(f, "  #########")
+    }
+}
+
+fn solutions(input: &str) -> Result<(Solution, Solution), Box<dyn Error>> {
+    let folded = Burrow::parse(input).ok_or("Can't parse input")?;
+    let unfolded = Burrow::unfold(input).ok_or("Can't parse input")?;
+
+    let solution_a = folded.organize().ok_or("Result A not found")?;
+    let solution_b = unfolded.organize().ok_or("Result B not found")?;
+
+    Ok((solution_a, solution_b))
+}
+
+pub(crate) fn solve(input: &str) -> Result<(usize, usize), Box<dyn Error>> {
+    let (solution_a, solution_b) = solutions(input)?;
+
+    Ok((solution_a.energy, solution_b.energy))
+}
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let filename = std::env::args().nth(1).ok_or("Invalid input")?;
+    let input = std::fs::read_to_string(filename)?;
+    let frames = std::env::args().any(|a| a == "--frames");
+
+    let (result_a, result_b) = if frames {
+        let (solution_a, solution_b) = solutions(&input)?;
+        let folded = Burrow::parse(&input).ok_or("Can't parse input")?;
+        let unfolded = Burrow::unfold(&input).ok_or("Can't parse input")?;
+
+        for (burrow, solution) in [(&folded, &solution_a), (&unfolded, &solution_b)] {
+            for state in solution.path.iter() {
+                println!("{}", burrow.frame(state));
+            }
+        }
+
+        (solution_a.energy, solution_b.energy)
+    } else {
+        solve(&input)?
+    };
+
+    println!("Task A: {}\nTask B: {}", result_a, result_b);
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const EXAMPLE: &str = "#############\n\
+                           #...........#\n\
+                           ###B#C#B#D###\n  \
+                           #A#D#C#A#\n  \
+                           #########";
+
+    #[test]
+    fn test_state() {
+        let state = State(0).set(3, Some(2)).set(26, Some(0));
+        assert_eq!(state.get(3), Some(2));
+        assert_eq!(state.get(26), Some(0));
+        assert_eq!(state.get(4), None);
+        assert_eq!(state.moved(3, 4).get(4), Some(2));
+        assert_eq!(state.moved(3, 4).get(3), None);
+    }
+
+    #[test]
+    fn test_frame() {
+        let burrow = Burrow::parse(EXAMPLE).unwrap();
+        let expected = "#############\n\
+                        #...........#\n\
+                        ###B#C#B#D###\n  \
+                        #A#D#C#A#\n  \
+                        #########\n";
+        assert_eq!(burrow.frame(&burrow.start).to_string(), expected);
+    }
+
+    #[test]
+    fn test_unfold() {
+        let burrow = Burrow::unfold(EXAMPLE).unwrap();
+        assert_eq!(burrow.depth, 4);
+        assert_eq!(burrow.start.get(burrow.slot(0, 1)), Some(3));
+        assert_eq!(burrow.start.get(burrow.slot(0, 3)), Some(0));
+
+        let mut lines = EXAMPLE.lines().collect::<Vec<_>>();
+        lines.splice(3..3, ["  #A#B#C#D#"; 4]);
+        let deep = lines.join("\n");
+        assert_eq!(Burrow::parse(&deep).map(|b| b.depth), Some(6));
+        assert!(Burrow::unfold(&deep).is_none());
+    }
+
+    #[test]
+    fn test_organize() {
+        let burrow = Burrow::parse(EXAMPLE).unwrap();
+        let solution = burrow.organize().unwrap();
+        assert_eq!(solution.energy, 12521);
+        assert_eq!(solution.path.first(), Some(&burrow.start));
+        assert_eq!(solution.path.last(), Some(&burrow.goal()));
+    }
+}
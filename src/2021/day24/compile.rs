@@ -0,0 +1,398 @@
+use super::{error::Fault, Op, Reg, Src};
+use std::fmt;
+
+pub const NAMES: [char; 4] = ['w', 'x', 'y', 'z'];
+
+pub fn slot(reg: &Reg) -> usize {
+    match reg {
+        Reg::W => 0,
+        Reg::X => 1,
+        Reg::Y => 2,
+        Reg::Z => 3,
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Instr {
+    Inp(usize),
+    Set(usize, i64),
+    Copy(usize, usize),
+    AddValue(usize, i64),
+    AddReg(usize, usize),
+    MulValue(usize, i64),
+    MulReg(usize, usize),
+    DivValue(usize, i64),
+    DivReg(usize, usize),
+    ModValue(usize, i64),
+    ModReg(usize, usize),
+    EqlValue(usize, i64),
+    EqlReg(usize, usize),
+    // always faults on the register, a modulo fault takes its dividend from it
+    Fault(usize, Fault),
+}
+
+impl Instr {
+    fn target(&self) -> usize {
+        match *self {
+            Self::Inp(r)
+            | Self::Set(r, _)
+            | Self::Copy(r, _)
+            | Self::AddValue(r, _)
+            | Self::AddReg(r, _)
+            | Self::MulValue(r, _)
+            | Self::MulReg(r, _)
+            | Self::DivValue(r, _)
+            | Self::DivReg(r, _)
+            | Self::ModValue(r, _)
+            | Self::ModReg(r, _)
+            | Self::EqlValue(r, _)
+            | Self::EqlReg(r, _)
+            | Self::Fault(r, _) => r,
+        }
+    }
+
+    // Whether the instruction faults for some register values, like an
+    // overflowing add or a modulo of a negative number.
+    fn faultable(&self) -> bool {
+        match *self {
+            Self::DivValue(_, v) => v == -1,
+            Self::AddValue(_, _)
+            | Self::AddReg(_, _)
+            | Self::MulValue(_, _)
+            | Self::MulReg(_, _)
+            | Self::DivReg(_, _)
+            | Self::ModValue(_, _)
+            | Self::ModReg(_, _) => true,
+            _ => false,
+        }
+    }
+
+    // Register mask read by the instruction.
+    fn uses(&self) -> u8 {
+        match *self {
+            Self::Inp(_) | Self::Set(_, _) | Self::Fault(_, _) => 0,
+            Self::Copy(_, q) => 1 << q,
+            Self::AddValue(r, _)
+            | Self::MulValue(r, _)
+            | Self::DivValue(r, _)
+            | Self::ModValue(r, _)
+            | Self::EqlValue(r, _) => 1 << r,
+            Self::AddReg(r, q)
+            | Self::MulReg(r, q)
+            | Self::DivReg(r, q)
+            | Self::ModReg(r, q)
+            | Self::EqlReg(r, q) => (1 << r) | (1 << q),
+        }
+    }
+}
+
+impl fmt::Display for Instr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            Self::Inp(r) => write!(f, "{} = input", NAMES[r]),
+            Self::Set(r, v) => write!(f, "{} = {}", NAMES[r], v),
+            Self::Copy(r, q) => write!(f, "{} = {}", NAMES[r], NAMES[q]),
+            Self::AddValue(r, v) => write!(f, "{} += {}", NAMES[r], v),
+            Self::AddReg(r, q) => write!(f, "{} += {}", NAMES[r], NAMES[q]),
+            Self::MulValue(r, v) => write!(f, "{} *= {}", NAMES[r], v),
+            Self::MulReg(r, q) => write!(f, "{} *= {}", NAMES[r], NAMES[q]),
+            Self::DivValue(r, v) => write!(f, "{} /= {}", NAMES[r], v),
+            Self::DivReg(r, q) => write!(f, "{} /= {}", NAMES[r], NAMES[q]),
+            Self::ModValue(r, v) => write!(f, "{} %= {}", NAMES[r], v),
+            Self::ModReg(r, q) => write!(f, "{} %= {}", NAMES[r], NAMES[q]),
+            Self::EqlValue(r, v) => write!(f, "{} = {} == {}", NAMES[r], NAMES[r], v),
+            Self::EqlReg(r, q) => write!(f, "{} = {} == {}", NAMES[r], NAMES[r], NAMES[q]),
+            Self::Fault(r, Fault::InvalidModulo(_, b)) => {
+                write!(f, "fault: invalid modulo {} % {}", NAMES[r], b)
+            }
+            Self::Fault(_, fault) => write!(f, "fault: {}", fault),
+        }
+    }
+}
+
+// Same semantics as `Computer::exec`, so faults are folded too.
+fn eval(op: &Op, a: i64, b: i64) -> Result<i64, Fault> {
+    match op {
+        Op::Inp(_) => Err(Fault::EmptyInput),
+        Op::Add(_, _) => a.checked_add(b).ok_or(Fault::Overflow),
+        Op::Mul(_, _) => a.checked_mul(b).ok_or(Fault::Overflow),
+        Op::Div(_, _) if b == 0 => Err(Fault::DivisionByZero),
+        Op::Div(_, _) => a.checked_div(b).ok_or(Fault::Overflow),
+        Op::Mod(_, _) if a < 0 || b <= 0 => Err(Fault::InvalidModulo(a, b)),
+        Op::Mod(_, _) => Ok(a % b),
+        Op::Eql(_, _) => Ok((a == b) as i64),
+    }
+}
+
+// Straight-line code with register slots resolved ahead of time. Register
+// values known at compile time are folded into constants and writes never
+// read afterwards are dropped.
+#[derive(Debug)]
+pub struct Programm {
+    code: Vec<Instr>,
+}
+
+impl Programm {
+    // Compiles a whole programm starting from zeroed registers, every
+    // register stays observable at the end.
+    pub fn compile(ops: &[Op]) -> Self {
+        Self::build(ops, [Some(0); 4], 0b1111)
+    }
+
+    // Compiles a part of a programm which starts with unknown registers,
+    // only registers in the `live` mask are kept correct at the end.
+    pub fn segment(ops: &[Op], live: u8) -> Self {
+        Self::build(ops, [None; 4], live)
+    }
+
+    fn build(ops: &[Op], mut known: [Option<i64>; 4], live: u8) -> Self {
+        let mut code = vec![];
+
+        for op in ops {
+            let (reg, src) = match op {
+                Op::Inp(reg) => {
+                    code.push(Instr::Inp(slot(reg)));
+                    known[slot(reg)] = None;
+                    continue;
+                }
+                Op::Add(reg, src)
+                | Op::Mul(reg, src)
+                | Op::Div(reg, src)
+                | Op::Mod(reg, src)
+                | Op::Eql(reg, src) => (slot(reg), src),
+            };
+
+            let (value, q) = match src {
+                Src::Value(v) => (Some(*v), None),
+                Src::Reg(q) => (known[slot(q)], Some(slot(q))),
+            };
+
+            if let Some((a, b)) = known[reg].zip(value) {
+                match eval(op, a, b) {
+                    Ok(folded) if known[reg] != Some(folded) => {
+                        code.push(Instr::Set(reg, folded));
+                        known[reg] = Some(folded);
+                    }
+                    Ok(_) => (),
+                    Err(fault) => {
+                        code.push(Instr::Fault(reg, fault));
+                        break;
+                    }
+                }
+                continue;
+            }
+
+            // nothing after a division by a literal zero or a modulo by a
+            // literal non-positive number is reachable
+            match (op, value) {
+                (Op::Div(_, _), Some(0)) => {
+                    code.push(Instr::Fault(reg, Fault::DivisionByZero));
+                    break;
+                }
+                (Op::Mod(_, _), Some(b)) if b <= 0 => {
+                    code.push(Instr::Fault(reg, Fault::InvalidModulo(0, b)));
+                    break;
+                }
+                _ => (),
+            }
+
+            let instr = match (op, known[reg], value, q) {
+                (Op::Add(_, _), _, Some(0), _)
+                | (Op::Mul(_, _), _, Some(1), _)
+                | (Op::Div(_, _), _, Some(1), _) => continue,
+                (Op::Mul(_, _), _, Some(0), _) | (Op::Mul(_, _), Some(0), _, _) => {
+                    known[reg] = Some(0);
+                    code.push(Instr::Set(reg, 0));
+                    continue;
+                }
+                (Op::Eql(_, _), _, _, Some(q)) if q == reg => {
+                    known[reg] = Some(1);
+                    code.push(Instr::Set(reg, 1));
+                    continue;
+                }
+                (Op::Add(_, _), Some(0), None, Some(q)) => Instr::Copy(reg, q),
+                (_, _, Some(v), _) => match op {
+                    Op::Add(_, _) => Instr::AddValue(reg, v),
+                    Op::Mul(_, _) => Instr::MulValue(reg, v),
+                    Op::Div(_, _) => Instr::DivValue(reg, v),
+                    Op::Mod(_, _) => Instr::ModValue(reg, v),
+                    _ => Instr::EqlValue(reg, v),
+                },
+                (_, _, None, Some(q)) => match op {
+                    Op::Add(_, _) => Instr::AddReg(reg, q),
+                    Op::Mul(_, _) => Instr::MulReg(reg, q),
+                    Op::Div(_, _) => Instr::DivReg(reg, q),
+                    Op::Mod(_, _) => Instr::ModReg(reg, q),
+                    _ => Instr::EqlReg(reg, q),
+                },
+                (_, _, None, None) => unreachable!("value sources are always known"),
+            };
+
+            known[reg] = None;
+            code.push(instr);
+        }
+
+        Self {
+            code: Self::eliminate(code, live),
+        }
+    }
+
+    fn eliminate(code: Vec<Instr>, mut live: u8) -> Vec<Instr> {
+        let mut result = vec![];
+
+        for instr in code.into_iter().rev() {
+            let def = 1 << instr.target();
+
+            // a fault makes every register value before it irrelevant
+            if let Instr::Fault(_, _) = instr {
+                live = 0;
+                result.push(instr);
+                continue;
+            }
+
+            // input is always consumed to keep the following digits in place,
+            // and a dead result can still fault
+            if live & def == 0 && !matches!(instr, Instr::Inp(_)) && !instr.faultable() {
+                continue;
+            }
+
+            live = (live & !def) | instr.uses();
+            result.push(instr);
+        }
+
+        result.reverse();
+        result
+    }
+
+    pub fn run(&self, regs: &mut [i64; 4], input: &[i64]) -> Result<(), Fault> {
+        self.exec(regs, input, |_, _| {})
+    }
+
+    pub fn trace(&self, regs: &mut [i64; 4], input: &[i64]) -> Result<(), Fault> {
+        self.exec(regs, input, |instr, regs| {
+            println!(
+                "{:14} -> [w: {} x: {} y: {} z: {}]",
+                instr.to_string(),
+                regs[0],
+                regs[1],
+                regs[2],
+                regs[3]
+            )
+        })
+    }
+
+    // Faults match `Computer::exec`, registers are left as they were right
+    // before the faulting instruction.
+    fn exec<F>(&self, regs: &mut [i64; 4], input: &[i64], mut observe: F) -> Result<(), Fault>
+    where
+        F: FnMut(&Instr, &[i64; 4]),
+    {
+        let mut input = input.iter();
+
+        for instr in &self.code {
+            let (r, b) = match *instr {
+                Instr::Inp(r) => {
+                    regs[r] = *input.next().ok_or(Fault::EmptyInput)?;
+                    observe(instr, regs);
+                    continue;
+                }
+                Instr::Fault(r, Fault::InvalidModulo(_, b)) => {
+                    return Err(Fault::InvalidModulo(regs[r], b))
+                }
+                Instr::Fault(_, fault) => return Err(fault),
+                Instr::Set(r, v)
+                | Instr::AddValue(r, v)
+                | Instr::MulValue(r, v)
+                | Instr::DivValue(r, v)
+                | Instr::ModValue(r, v)
+                | Instr::EqlValue(r, v) => (r, v),
+                Instr::Copy(r, q)
+                | Instr::AddReg(r, q)
+                | Instr::MulReg(r, q)
+                | Instr::DivReg(r, q)
+                | Instr::ModReg(r, q)
+                | Instr::EqlReg(r, q) => (r, regs[q]),
+            };
+            let a = regs[r];
+
+            regs[r] = match *instr {
+                Instr::Set(_, _) | Instr::Copy(_, _) => b,
+                Instr::AddValue(_, _) | Instr::AddReg(_, _) => {
+                    a.checked_add(b).ok_or(Fault::Overflow)?
+                }
+                Instr::MulValue(_, _) | Instr::MulReg(_, _) => {
+                    a.checked_mul(b).ok_or(Fault::Overflow)?
+                }
+                Instr::DivValue(_, _) | Instr::DivReg(_, _) if b == 0 => {
+                    return Err(Fault::DivisionByZero)
+                }
+                Instr::DivValue(_, _) | Instr::DivReg(_, _) => {
+                    a.checked_div(b).ok_or(Fault::Overflow)?
+                }
+                Instr::ModValue(_, _) | Instr::ModReg(_, _) if a < 0 || b <= 0 => {
+                    return Err(Fault::InvalidModulo(a, b))
+                }
+                Instr::ModValue(_, _) | Instr::ModReg(_, _) => a % b,
+                _ => (a == b) as i64,
+            };
+
+            observe(instr, regs);
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::super::Computer;
+    use super::*;
+
+    const PROGRAMM: &str = "inp w\nmul x 0\nadd x z\nmod x 26\ndiv z 1\nadd x 12\neql x w\n\
+                            eql x 0\nmul y 0\nadd y 25\nmul y x\nadd y 1\nmul z y\nmul y 0\n\
+                            add y w\nadd y 7\nmul y x\nadd z y\ninp x\nadd z x\nmod z 5";
+
+    #[test]
+    fn test_compile() {
+        let ops = PROGRAMM
+            .lines()
+            .map(Op::parse)
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        let programm = Programm::compile(&ops);
+
+        // the dead `y *= x` and `y += 1` stay with their operands, they could overflow
+        assert_eq!(programm.code.len(), 14);
+
+        for a in 1..=9 {
+            for b in 1..=9 {
+                let mut computer = Computer::new();
+                computer.run(&ops, &[a, b]).unwrap();
+
+                let mut regs = [0; 4];
+                programm.run(&mut regs, &[a, b]).unwrap();
+
+                assert_eq!(regs, [computer.w, computer.x, computer.y, computer.z]);
+            }
+        }
+
+        let ops = ["add x 5", "div x 0", "inp w", "mod z 0"]
+            .iter()
+            .map(|line| Op::parse(line))
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+
+        let programm = Programm::compile(&ops);
+        assert_eq!(programm.code, vec![Instr::Fault(1, Fault::DivisionByZero)]);
+
+        let programm = Programm::segment(&ops[2..], 0b1111);
+        assert_eq!(
+            programm.code,
+            vec![Instr::Inp(0), Instr::Fault(3, Fault::InvalidModulo(0, 0))]
+        );
+        assert_eq!(
+            programm.run(&mut [0, 0, 0, 7], &[1]),
+            Err(Fault::InvalidModulo(7, 0))
+        );
+    }
+}
@@ -0,0 +1,179 @@
+use super::{compile::slot, monad, Op, Reg, Src};
+
+// Constant operands replaced by parameter names in the MONAD listing.
+const PARAMS: [(usize, &str); 3] = [(4, "DIV"), (5, "CHECK"), (15, "OFFSET")];
+
+fn operand(op: &Op) -> Option<&Src> {
+    match op {
+        Op::Inp(_) => None,
+        Op::Add(_, src) | Op::Mul(_, src) | Op::Div(_, src) | Op::Mod(_, src) | Op::Eql(_, src) => {
+            Some(src)
+        }
+    }
+}
+
+fn target(op: &Op) -> &Reg {
+    match op {
+        Op::Inp(reg)
+        | Op::Add(reg, _)
+        | Op::Mul(reg, _)
+        | Op::Div(reg, _)
+        | Op::Mod(reg, _)
+        | Op::Eql(reg, _) => reg,
+    }
+}
+
+fn same(a: &Op, b: &Op) -> bool {
+    slot(target(a)) == slot(target(b))
+}
+
+// Turns ops into assignments, folding `mul r 0` into a plain assignment,
+// `eql r s` followed by `eql r 0` into `!=` and dropping no-op arithmetic.
+pub fn statements(ops: &[Op], param: impl Fn(usize) -> Option<&'static str>) -> Vec<String> {
+    let value = |idx: usize| match param(idx) {
+        Some(name) => name.to_owned(),
+        None => operand(&ops[idx])
+            .map(|src| src.to_string())
+            .unwrap_or_default(),
+    };
+
+    let mut result = vec![];
+    let mut idx = 0;
+
+    while idx < ops.len() {
+        let op = &ops[idx];
+        let reg = target(op);
+        let next = ops.get(idx + 1).filter(|next| same(op, next));
+
+        let (line, len) = match (op, next) {
+            // adding the cleared register to itself keeps it at zero
+            (Op::Mul(_, Src::Value(0)), Some(Op::Add(_, Src::Reg(src))))
+                if slot(src) == slot(reg) =>
+            {
+                (format!("{} = 0", reg), 2)
+            }
+            (Op::Mul(_, Src::Value(0)), Some(Op::Add(_, _))) => {
+                (format!("{} = {}", reg, value(idx + 1)), 2)
+            }
+            (Op::Eql(_, _), Some(Op::Eql(_, Src::Value(0)))) => {
+                (format!("{} = {} != {}", reg, reg, value(idx)), 2)
+            }
+            (Op::Mul(_, Src::Value(0)), _) => (format!("{} = 0", reg), 1),
+            (Op::Add(_, Src::Value(0)), _)
+            | (Op::Mul(_, Src::Value(1)), _)
+            | (Op::Div(_, Src::Value(1)), _)
+                if param(idx).is_none() =>
+            {
+                idx += 1;
+                continue;
+            }
+            (Op::Inp(_), _) => (format!("{} = input()", reg), 1),
+            (Op::Add(_, _), _) => (format!("{} += {}", reg, value(idx)), 1),
+            (Op::Mul(_, _), _) => (format!("{} *= {}", reg, value(idx)), 1),
+            (Op::Div(_, _), _) => (format!("{} /= {}", reg, value(idx)), 1),
+            (Op::Mod(_, _), _) => (format!("{} %= {}", reg, value(idx)), 1),
+            (Op::Eql(_, _), _) => (format!("{} = {} == {}", reg, reg, value(idx)), 1),
+        };
+
+        result.push(line);
+        idx += len;
+    }
+
+    result
+}
+
+fn monad_listing(programm: &[Op], blocks: &[monad::Block]) -> String {
+    let param = |idx| {
+        PARAMS
+            .iter()
+            .find(|(p, _)| *p == idx)
+            .map(|(_, name)| *name)
+    };
+    let mut lines = vec!["fn digit(z: i64) -> i64 {".to_owned()];
+
+    for statement in statements(&programm[..programm.len() / blocks.len()], param) {
+        lines.push(format!("    {}", statement));
+    }
+
+    lines.push("    z".to_owned());
+    lines.push("}".to_owned());
+    lines.push(String::new());
+    lines.push("digit    DIV  CHECK  OFFSET".to_owned());
+
+    for (idx, block) in blocks.iter().enumerate() {
+        lines.push(format!(
+            "{:5} {:6} {:6} {:7}",
+            idx, block.div, block.check, block.offset
+        ));
+    }
+
+    if let Some(constraints) = monad::constraints(blocks) {
+        lines.push(String::new());
+        for c in constraints {
+            let sign = if c.delta < 0 { '-' } else { '+' };
+            lines.push(format!(
+                "d[{}] == d[{}] {} {}",
+                c.right,
+                c.left,
+                sign,
+                c.delta.abs()
+            ));
+        }
+    }
+
+    lines.join("\n")
+}
+
+fn generic_listing(programm: &[Op]) -> String {
+    let mut lines = vec![];
+    let mut start = 0;
+
+    for (digit, end) in (1..=programm.len())
+        .filter(|end| *end == programm.len() || matches!(programm[*end], Op::Inp(_)))
+        .enumerate()
+    {
+        lines.push(format!("// segment {}", digit));
+        lines.extend(statements(&programm[start..end], |_| None));
+        start = end;
+    }
+
+    lines.join("\n")
+}
+
+pub fn listing(programm: &[Op]) -> String {
+    match monad::blocks(programm) {
+        Some(blocks) => monad_listing(programm, &blocks),
+        None => generic_listing(programm),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_statements() {
+        let ops = "inp w\nmul x 0\nadd x z\nmod x 26\ndiv z 1\nadd x 12\neql x w\neql x 0\nmul y 0"
+            .lines()
+            .map(Op::parse)
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+
+        let expected = [
+            "w = input()",
+            "x = z",
+            "x %= 26",
+            "x += 12",
+            "x = x != w",
+            "y = 0",
+        ];
+        assert_eq!(statements(&ops, |_| None), expected);
+
+        let ops = ["mul y 0", "add y y", "add y w"]
+            .iter()
+            .map(|line| Op::parse(line))
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        assert_eq!(statements(&ops, |_| None), ["y = 0", "y += w"]);
+    }
+}
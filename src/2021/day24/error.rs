@@ -0,0 +1,71 @@
+use super::Computer;
+use std::{error::Error, fmt};
+
+#[derive(Debug, PartialEq)]
+pub enum ParseError {
+    Empty,
+    UnknownMnemonic(String),
+    MissingRegister,
+    InvalidRegister(String),
+    MissingOperand,
+    InvalidOperand(String),
+    UnexpectedOperand(String),
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Empty => write!(f, "empty instruction"),
+            Self::UnknownMnemonic(op) => write!(f, "unknown mnemonic {:?}", op),
+            Self::MissingRegister => write!(f, "missing register"),
+            Self::InvalidRegister(reg) => write!(f, "invalid register {:?}", reg),
+            Self::MissingOperand => write!(f, "missing second operand"),
+            Self::InvalidOperand(src) => write!(f, "invalid operand {:?}", src),
+            Self::UnexpectedOperand(src) => write!(f, "unexpected operand {:?}", src),
+        }
+    }
+}
+
+impl Error for ParseError {}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Fault {
+    EmptyInput,
+    DivisionByZero,
+    InvalidModulo(i64, i64),
+    Overflow,
+}
+
+impl fmt::Display for Fault {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::EmptyInput => write!(f, "empty input"),
+            Self::DivisionByZero => write!(f, "division by zero"),
+            Self::InvalidModulo(a, b) => write!(f, "invalid modulo {} % {}", a, b),
+            Self::Overflow => write!(f, "arithmetic overflow"),
+        }
+    }
+}
+
+impl Error for Fault {}
+
+// A fault together with the failed instruction and the registers as they
+// were right before it.
+#[derive(Debug)]
+pub struct RuntimeError {
+    pub index: usize,
+    pub fault: Fault,
+    pub registers: Computer,
+}
+
+impl fmt::Display for RuntimeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "instruction {}: {} {}",
+            self.index, self.fault, self.registers
+        )
+    }
+}
+
+impl Error for RuntimeError {}
@@ -0,0 +1,501 @@
+mod compile;
+mod decompile;
+mod error;
+mod monad;
+mod search;
+
+use advent2021::{
+    check::{self, Violation},
+    repl::{self, Session},
+};
+use compile::Programm;
+use error::{Fault, ParseError, RuntimeError};
+use search::Search;
+use std::{collections::VecDeque, error::Error, fmt::Display};
+
+#[derive(Debug)]
+enum Reg {
+    W,
+    X,
+    Y,
+    Z,
+}
+
+impl Reg {
+    pub fn parse(input: &str) -> Option<Self> {
+        match input {
+            "w" => Some(Self::W),
+            "x" => Some(Self::X),
+            "y" => Some(Self::Y),
+            "z" => Some(Self::Z),
+            _ => None,
+        }
+    }
+}
+
+impl Display for Reg {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::W => write!(f, "w"),
+            Self::X => write!(f, "x"),
+            Self::Y => write!(f, "y"),
+            Self::Z => write!(f, "z"),
+        }
+    }
+}
+
+#[derive(Debug)]
+enum Src {
+    Reg(Reg),
+    Value(i64),
+}
+
+impl Src {
+    pub fn parse(input: &str) -> Option<Self> {
+        Reg::parse(input)
+            .map(Self::Reg)
+            .or_else(|| input.parse::<i64>().ok().map(Self::Value))
+    }
+}
+
+impl Display for Src {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Reg(reg) => write!(f, "{}", reg),
+            Self::Value(val) => write!(f, "{}", val),
+        }
+    }
+}
+
+#[derive(Debug)]
+enum Op {
+    Inp(Reg),
+    Add(Reg, Src),
+    Mul(Reg, Src),
+    Div(Reg, Src),
+    Mod(Reg, Src),
+    Eql(Reg, Src),
+}
+
+impl Op {
+    pub fn parse(input: &str) -> Result<Self, ParseError> {
+        let mut items = input.split_whitespace();
+        let op = items.next().ok_or(ParseError::Empty)?;
+
+        let binary = match op {
+            "inp" => false,
+            "add" | "mul" | "div" | "mod" | "eql" => true,
+            _ => return Err(ParseError::UnknownMnemonic(op.to_owned())),
+        };
+
+        let raw = items.next().ok_or(ParseError::MissingRegister)?;
+        let reg = Reg::parse(raw).ok_or_else(|| ParseError::InvalidRegister(raw.to_owned()))?;
+
+        let src = if binary {
+            let raw = items.next().ok_or(ParseError::MissingOperand)?;
+            Some(Src::parse(raw).ok_or_else(|| ParseError::InvalidOperand(raw.to_owned()))?)
+        } else {
+            None
+        };
+
+        if let Some(extra) = items.next() {
+            return Err(ParseError::UnexpectedOperand(extra.to_owned()));
+        }
+
+        match (op, src) {
+            ("add", Some(src)) => Ok(Self::Add(reg, src)),
+            ("mul", Some(src)) => Ok(Self::Mul(reg, src)),
+            ("div", Some(src)) => Ok(Self::Div(reg, src)),
+            ("mod", Some(src)) => Ok(Self::Mod(reg, src)),
+            ("eql", Some(src)) => Ok(Self::Eql(reg, src)),
+            _ => Ok(Self::Inp(reg)),
+        }
+    }
+}
+
+impl Display for Op {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Inp(reg) => write!(f, "* inp {}", reg),
+            Self::Add(reg, src) => write!(f, "add {} {}", reg, src),
+            Self::Mul(reg, src) => write!(f, "mul {} {}", reg, src),
+            Self::Div(reg, src) => write!(f, "div {} {}", reg, src),
+            Self::Mod(reg, src) => write!(f, "mod {} {}", reg, src),
+            Self::Eql(reg, src) => write!(f, "eql {} {}", reg, src),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct Computer {
+    w: i64,
+    x: i64,
+    y: i64,
+    z: i64,
+}
+
+impl Computer {
+    pub fn new() -> Self {
+        Self {
+            w: 0,
+            x: 0,
+            y: 0,
+            z: 0,
+        }
+    }
+
+    pub fn run(&mut self, programm: &[Op], input: &[i64]) -> Result<(), RuntimeError> {
+        let mut input = input.iter().copied();
+
+        for (index, op) in programm.iter().enumerate() {
+            self.exec(op, &mut input).map_err(|fault| RuntimeError {
+                index,
+                fault,
+                registers: self.clone(),
+            })?;
+        }
+
+        Ok(())
+    }
+
+    // Faults are detected before the instruction changes any register.
+    pub fn exec(&mut self, op: &Op, input: &mut impl Iterator<Item = i64>) -> Result<(), Fault> {
+        match op {
+            Op::Inp(reg) => {
+                let src = input.next().ok_or(Fault::EmptyInput)?;
+                let target = self.reg(reg);
+                *target = src;
+            }
+
+            Op::Add(reg, src) => {
+                let src = self.src(src);
+                let target = self.reg(reg);
+                *target = target.checked_add(src).ok_or(Fault::Overflow)?;
+            }
+
+            Op::Mul(reg, src) => {
+                let src = self.src(src);
+                let target = self.reg(reg);
+                *target = target.checked_mul(src).ok_or(Fault::Overflow)?;
+            }
+
+            Op::Div(reg, src) => {
+                let src = self.src(src);
+                if src == 0 {
+                    return Err(Fault::DivisionByZero);
+                }
+                let target = self.reg(reg);
+                *target = target.checked_div(src).ok_or(Fault::Overflow)?;
+            }
+
+            Op::Mod(reg, src) => {
+                let src = self.src(src);
+                let target = self.reg(reg);
+                if *target < 0 || src <= 0 {
+                    return Err(Fault::InvalidModulo(*target, src));
+                }
+                *target %= src;
+            }
+
+            Op::Eql(reg, src) => {
+                let src = self.src(src);
+                let target = self.reg(reg);
+
+                if *target == src {
+                    *target = 1
+                } else {
+                    *target = 0
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn reg(&mut self, reg: &Reg) -> &mut i64 {
+        match reg {
+            Reg::W => &mut self.w,
+            Reg::X => &mut self.x,
+            Reg::Y => &mut self.y,
+            Reg::Z => &mut self.z,
+        }
+    }
+
+    pub fn value(&self, reg: &Reg) -> i64 {
+        match reg {
+            Reg::W => self.w,
+            Reg::X => self.x,
+            Reg::Y => self.y,
+            Reg::Z => self.z,
+        }
+    }
+
+    pub fn src(&self, src: &Src) -> i64 {
+        match src {
+            Src::Value(val) => *val,
+            Src::Reg(reg) => self.value(reg),
+        }
+    }
+}
+
+impl Display for Computer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "[w: {} x: {} y: {} z: {}]",
+            self.w, self.x, self.y, self.z
+        )
+    }
+}
+
+fn check(input: &str) -> Vec<Violation> {
+    let mut violations = vec![];
+
+    for (idx, line) in input.lines().enumerate() {
+        if let Err(e) = Op::parse(line) {
+            violations.push(Violation::new(idx + 1, e.to_string()));
+        }
+    }
+
+    violations
+}
+
+fn parse_digits(input: &str) -> Option<VecDeque<i64>> {
+    input
+        .chars()
+        .map(|c| c.to_digit(10).map(|d| d as i64))
+        .collect()
+}
+
+struct Repl {
+    programm: Vec<Op>,
+    computer: Computer,
+    input: VecDeque<i64>,
+    pc: usize,
+}
+
+impl Repl {
+    pub fn new(programm: Vec<Op>) -> Self {
+        Self {
+            programm,
+            computer: Computer::new(),
+            input: VecDeque::new(),
+            pc: 0,
+        }
+    }
+
+    fn step(&mut self) -> Result<String, Box<dyn Error>> {
+        let op = self.programm.get(self.pc).ok_or("Programm finished")?;
+        let input = &mut self.input;
+
+        self.computer
+            .exec(op, &mut std::iter::from_fn(|| input.pop_front()))
+            .map_err(|fault| format!("instruction {}: {}", self.pc, fault))?;
+        self.pc += 1;
+
+        Ok(format!(
+            "{:4} {:10} -> {}",
+            self.pc - 1,
+            format!("{}", op),
+            self.computer
+        ))
+    }
+}
+
+impl Session for Repl {
+    fn help(&self) -> &str {
+        "eval <digits>  reset the computer with the given input digits\n\
+         step [k]       execute the next k instructions (default 1)\n\
+         show           instruction counter, next instruction and registers\n\
+         query <reg>    value of a single register"
+    }
+
+    fn eval(&mut self, command: &str, args: &[&str]) -> Result<String, Box<dyn Error>> {
+        match command {
+            "eval" => {
+                let raw = args.first().ok_or("Missing input digits")?;
+                self.input = parse_digits(raw).ok_or("Invalid input digits")?;
+                self.computer = Computer::new();
+                self.pc = 0;
+                Ok(format!("{}", self.computer))
+            }
+            "step" => {
+                let k: usize = repl::arg_or(args, 0, 1)?;
+                let mut lines = vec![];
+                for _ in 0..k {
+                    match self.step() {
+                        Ok(line) => lines.push(line),
+                        Err(e) => {
+                            lines.push(format!("Error: {}", e));
+                            break;
+                        }
+                    }
+                }
+                Ok(lines.join("\n"))
+            }
+            "show" => {
+                let next = match self.programm.get(self.pc) {
+                    Some(op) => format!("{}", op),
+                    None => "end".to_owned(),
+                };
+                Ok(format!("{:4} {:10} -> {}", self.pc, next, self.computer))
+            }
+            "query" => {
+                let reg = args
+                    .first()
+                    .and_then(|r| Reg::parse(r))
+                    .ok_or("Invalid register")?;
+                Ok(format!("{}: {}", reg, self.computer.value(&reg)))
+            }
+            _ => Err(format!("Unknown command: {}", command).into()),
+        }
+    }
+}
+
+// Solves the MONAD blocks directly unless `forced` or the programm has no
+// such structure, then falls back to `search`. Both models are verified.
+fn models(
+    commands: &[Op],
+    mut search: Search,
+    forced: bool,
+) -> Result<(Vec<i64>, Vec<i64>), Box<dyn Error>> {
+    let (largest, smallest) = match monad::blocks(commands) {
+        Some(blocks) if !forced => {
+            let constraints = monad::constraints(&blocks).ok_or("Unbalanced MONAD blocks")?;
+            (monad::largest(&constraints), monad::smallest(&constraints))
+        }
+        _ => (search.largest(), search.smallest()),
+    };
+
+    let largest = largest.ok_or("Result A not found")?;
+    let smallest = smallest.ok_or("Result B not found")?;
+
+    for digits in [&largest, &smallest] {
+        let mut computer = Computer::new();
+        computer.run(commands, digits)?;
+
+        if computer.z != 0 {
+            return Err(format!("Model number {:?} rejected", digits).into());
+        }
+    }
+
+    Ok((largest, smallest))
+}
+
+fn parse(input: &str) -> Result<Vec<Op>, Box<dyn Error>> {
+    let commands = input
+        .lines()
+        .enumerate()
+        .map(|(idx, line)| Op::parse(line).map_err(|e| format!("line {}: {}", idx + 1, e)))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(commands)
+}
+
+fn number(digits: &[i64]) -> i64 {
+    digits.iter().fold(0, |acc, d| acc * 10 + d)
+}
+
+pub(crate) fn solve(input: &str) -> Result<(i64, i64), Box<dyn Error>> {
+    let commands = parse(input)?;
+    let (largest, smallest) = models(&commands, Search::new(&commands), false)?;
+
+    Ok((number(&largest), number(&smallest)))
+}
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let filename = std::env::args().nth(1).ok_or("Invalid input")?;
+    let input = std::fs::read_to_string(filename)?;
+
+    if std::env::args().any(|a| a == "--check") {
+        return check::report(&check(&input));
+    }
+
+    let commands = parse(&input)?;
+
+    if std::env::args().any(|a| a == "--repl") {
+        return repl::run(&mut Repl::new(commands));
+    }
+
+    if std::env::args().any(|a| a == "--decompile") {
+        println!("{}", decompile::listing(&commands));
+        return Ok(());
+    }
+
+    let args = std::env::args().collect::<Vec<_>>();
+    let flagged = ["--bound", "--progress", "--search", "--trace"];
+
+    let (result_a, result_b) = if args.iter().any(|a| flagged.contains(&a.as_str())) {
+        let bound = args
+            .iter()
+            .position(|a| a == "--bound")
+            .map(|idx| args.get(idx + 1).and_then(|b| b.parse::<i64>().ok()))
+            .map(|bound| bound.ok_or("Invalid bound"))
+            .transpose()?;
+
+        let search = Search::new(&commands)
+            .with_bound(bound)
+            .with_progress(args.iter().any(|a| a == "--progress"));
+        let forced = args.iter().any(|a| a == "--search");
+        let (largest, smallest) = models(&commands, search, forced)?;
+
+        if args.iter().any(|a| a == "--trace") {
+            let programm = Programm::compile(&commands);
+            for digits in [&largest, &smallest] {
+                programm.trace(&mut [0; 4], digits)?;
+            }
+        }
+
+        (number(&largest), number(&smallest))
+    } else {
+        solve(&input)?
+    };
+
+    println!("Task A: {}, Task B: {}", result_a, result_b);
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(
+            Op::parse("sub x 1").err(),
+            Some(ParseError::UnknownMnemonic("sub".to_owned()))
+        );
+        assert_eq!(Op::parse("add x").err(), Some(ParseError::MissingOperand));
+        assert_eq!(
+            Op::parse("inp a").err(),
+            Some(ParseError::InvalidRegister("a".to_owned()))
+        );
+        assert!(Op::parse("eql x -3").is_ok());
+    }
+
+    #[test]
+    fn test_runtime_errors() {
+        let programm = ["inp w", "add x -1", "mod x w", "div z w"]
+            .iter()
+            .map(|line| Op::parse(line))
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+
+        let error = Computer::new().run(&programm, &[]).unwrap_err();
+        assert_eq!((error.index, error.fault), (0, Fault::EmptyInput));
+
+        let error = Computer::new().run(&programm, &[3]).unwrap_err();
+        assert_eq!((error.index, error.fault), (2, Fault::InvalidModulo(-1, 3)));
+        assert_eq!(error.registers.w, 3);
+
+        let error = Computer::new().run(&programm[3..], &[]).unwrap_err();
+        assert_eq!((error.index, error.fault), (0, Fault::DivisionByZero));
+
+        let mut computer = Computer::new();
+        computer.x = i64::MIN;
+        let error = computer.run(&programm[1..2], &[]).unwrap_err();
+        assert_eq!(error.fault, Fault::Overflow);
+        assert_eq!(computer.x, i64::MIN);
+    }
+}
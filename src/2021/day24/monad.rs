@@ -0,0 +1,205 @@
+use super::{Op, Reg, Src};
+
+// Every digit is processed by the same 18 instructions, only the divisor of z,
+// the constant compared against the digit and the constant pushed onto z differ.
+const TEMPLATE: [&str; 18] = [
+    "* inp w", "mul x 0", "add x z", "mod x 26", "", "", "eql x w", "eql x 0", "mul y 0",
+    "add y 25", "mul y x", "add y 1", "mul z y", "mul y 0", "add y w", "", "mul y x", "add z y",
+];
+
+pub const DIGITS: usize = 14;
+
+#[derive(Debug, PartialEq)]
+pub struct Block {
+    pub div: i64,
+    pub check: i64,
+    pub offset: i64,
+}
+
+impl Block {
+    fn parse(ops: &[Op]) -> Option<Self> {
+        if ops.len() != TEMPLATE.len() {
+            return None;
+        }
+
+        for (op, expected) in ops.iter().zip(TEMPLATE.iter()) {
+            if !expected.is_empty() && op.to_string() != *expected {
+                return None;
+            }
+        }
+
+        match (&ops[4], &ops[5], &ops[15]) {
+            (
+                Op::Div(Reg::Z, Src::Value(div)),
+                Op::Add(Reg::X, Src::Value(check)),
+                Op::Add(Reg::Y, Src::Value(offset)),
+            ) => Some(Self {
+                div: *div,
+                check: *check,
+                offset: *offset,
+            }),
+            _ => None,
+        }
+    }
+}
+
+pub fn blocks(programm: &[Op]) -> Option<Vec<Block>> {
+    let starts = programm
+        .iter()
+        .enumerate()
+        .filter(|(_, op)| matches!(op, Op::Inp(_)))
+        .map(|(idx, _)| idx)
+        .collect::<Vec<_>>();
+
+    if starts.len() != DIGITS || starts[0] != 0 {
+        return None;
+    }
+
+    starts
+        .iter()
+        .zip(
+            starts
+                .iter()
+                .skip(1)
+                .chain(std::iter::once(&programm.len())),
+        )
+        .map(|(start, end)| Block::parse(&programm[*start..*end]))
+        .collect()
+}
+
+// digit[right] == digit[left] + delta
+#[derive(Debug, PartialEq)]
+pub struct Constraint {
+    pub left: usize,
+    pub right: usize,
+    pub delta: i64,
+}
+
+// z works as a stack of base 26 numbers: blocks with `div z 1` always push
+// digit + offset, blocks with `div z 26` pop it and must not push again,
+// which only happens when their digit equals the popped value + check.
+pub fn constraints(blocks: &[Block]) -> Option<Vec<Constraint>> {
+    let mut stack = vec![];
+    let mut result = vec![];
+
+    for (idx, block) in blocks.iter().enumerate() {
+        match block.div {
+            1 => stack.push((idx, block.offset)),
+            26 => {
+                let (left, offset) = stack.pop()?;
+                result.push(Constraint {
+                    left,
+                    right: idx,
+                    delta: offset + block.check,
+                });
+            }
+            _ => return None,
+        }
+    }
+
+    if stack.is_empty() {
+        Some(result)
+    } else {
+        None
+    }
+}
+
+fn model(constraints: &[Constraint], pick: impl Fn(i64) -> (i64, i64)) -> Option<Vec<i64>> {
+    let mut digits = vec![0; constraints.len() * 2];
+
+    for c in constraints {
+        let (left, right) = pick(c.delta);
+        digits[c.left] = left;
+        digits[c.right] = right;
+    }
+
+    if digits.iter().all(|d| (1..=9).contains(d)) {
+        Some(digits)
+    } else {
+        None
+    }
+}
+
+pub fn largest(constraints: &[Constraint]) -> Option<Vec<i64>> {
+    model(constraints, |delta| {
+        if delta >= 0 {
+            (9 - delta, 9)
+        } else {
+            (9, 9 + delta)
+        }
+    })
+}
+
+pub fn smallest(constraints: &[Constraint]) -> Option<Vec<i64>> {
+    model(constraints, |delta| {
+        if delta >= 0 {
+            (1, 1 + delta)
+        } else {
+            (1 - delta, 1)
+        }
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn block(div: i64, check: i64, offset: i64) -> Vec<Op> {
+        TEMPLATE
+            .iter()
+            .enumerate()
+            .map(|(idx, line)| match idx {
+                4 => format!("div z {}", div),
+                5 => format!("add x {}", check),
+                15 => format!("add y {}", offset),
+                _ => line.trim_start_matches("* ").to_owned(),
+            })
+            .map(|line| Op::parse(&line).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn test_constraints() {
+        let params = [(1, 12, 4), (1, 11, 2), (26, -5, 0), (26, -1, 7)];
+        let programm = params
+            .iter()
+            .flat_map(|(div, check, offset)| block(*div, *check, *offset))
+            .collect::<Vec<_>>();
+
+        let parsed = programm
+            .chunks(TEMPLATE.len())
+            .map(Block::parse)
+            .collect::<Option<Vec<_>>>()
+            .unwrap();
+
+        assert_eq!(
+            parsed[2],
+            Block {
+                div: 26,
+                check: -5,
+                offset: 0
+            }
+        );
+
+        let constraints = constraints(&parsed).unwrap();
+        assert_eq!(
+            constraints[0],
+            Constraint {
+                left: 1,
+                right: 2,
+                delta: -3
+            }
+        );
+        assert_eq!(
+            constraints[1],
+            Constraint {
+                left: 0,
+                right: 3,
+                delta: 3
+            }
+        );
+
+        assert_eq!(largest(&constraints), Some(vec![6, 9, 6, 9]));
+        assert_eq!(smallest(&constraints), Some(vec![1, 4, 1, 4]));
+    }
+}
@@ -0,0 +1,220 @@
+use super::{
+    compile::{slot, Programm},
+    Op, Reg, Src,
+};
+use std::collections::HashSet;
+
+fn bit(reg: &Reg) -> u8 {
+    1 << slot(reg)
+}
+
+// Registers the ops read before writing them, and registers they write.
+fn usage(ops: &[Op]) -> (u8, u8) {
+    let mut read = 0;
+    let mut written = 0;
+
+    for op in ops {
+        let (reg, src) = match op {
+            Op::Inp(reg) => {
+                written |= bit(reg);
+                continue;
+            }
+            Op::Add(reg, src)
+            | Op::Mul(reg, src)
+            | Op::Div(reg, src)
+            | Op::Mod(reg, src)
+            | Op::Eql(reg, src) => (reg, src),
+        };
+
+        if let Src::Reg(src) = src {
+            read |= bit(src) & !written;
+        }
+
+        // `mul r 0` clears the register whatever it held before
+        if !matches!(op, Op::Mul(_, Src::Value(0))) {
+            read |= bit(reg) & !written;
+        }
+
+        written |= bit(reg);
+    }
+
+    (read, written)
+}
+
+fn mask(mut regs: [i64; 4], live: u8) -> [i64; 4] {
+    for (idx, reg) in regs.iter_mut().enumerate() {
+        if live & (1 << idx) == 0 {
+            *reg = 0;
+        }
+    }
+
+    regs
+}
+
+// Depth-first search over the input digits, one `inp` delimited segment at a
+// time. Registers not read by the rest of the programm are cleared, so states
+// collapse to `(segment, z)` for MONAD-like programms and dead states can be
+// remembered across both searches.
+pub struct Search {
+    prefix: Programm,
+    segments: Vec<Programm>,
+    live: Vec<u8>,
+    bound: Option<i64>,
+    progress: bool,
+    dead: HashSet<(usize, [i64; 4])>,
+    visited: usize,
+}
+
+impl Search {
+    pub fn new(programm: &[Op]) -> Self {
+        let mut starts = programm
+            .iter()
+            .enumerate()
+            .filter(|(_, op)| matches!(op, Op::Inp(_)))
+            .map(|(idx, _)| idx)
+            .collect::<Vec<_>>();
+
+        let prefix = &programm[..starts.first().copied().unwrap_or(programm.len())];
+        starts.push(programm.len());
+
+        let segments = starts
+            .windows(2)
+            .map(|w| &programm[w[0]..w[1]])
+            .collect::<Vec<_>>();
+
+        let mut live = vec![bit(&Reg::Z)];
+        for segment in segments.iter().rev() {
+            let (read, written) = usage(segment);
+            let after = live.last().copied().unwrap_or(0);
+            live.push(read | (after & !written));
+        }
+        live.reverse();
+
+        let segments = segments
+            .iter()
+            .enumerate()
+            .map(|(idx, segment)| Programm::segment(segment, live[idx + 1]))
+            .collect();
+
+        Self {
+            prefix: Programm::compile(prefix),
+            segments,
+            live,
+            bound: None,
+            progress: false,
+            dead: HashSet::new(),
+            visited: 0,
+        }
+    }
+
+    pub fn with_bound(self, bound: Option<i64>) -> Self {
+        Self { bound, ..self }
+    }
+
+    pub fn with_progress(self, progress: bool) -> Self {
+        Self { progress, ..self }
+    }
+
+    pub fn largest(&mut self) -> Option<Vec<i64>> {
+        self.find(&[9, 8, 7, 6, 5, 4, 3, 2, 1])
+    }
+
+    pub fn smallest(&mut self) -> Option<Vec<i64>> {
+        self.find(&[1, 2, 3, 4, 5, 6, 7, 8, 9])
+    }
+
+    fn find(&mut self, digits: &[i64]) -> Option<Vec<i64>> {
+        let mut regs = [0; 4];
+        self.prefix.run(&mut regs, &[]).ok()?;
+
+        let mut path = vec![];
+        if self.visit(0, regs, digits, &mut path) {
+            Some(path)
+        } else {
+            None
+        }
+    }
+
+    fn visit(&mut self, idx: usize, regs: [i64; 4], digits: &[i64], path: &mut Vec<i64>) -> bool {
+        if idx == self.segments.len() {
+            return regs[3] == 0;
+        }
+
+        let state = mask(regs, self.live[idx]);
+
+        if self.bound.is_some_and(|bound| state[3].abs() > bound) {
+            return false;
+        }
+
+        if self.dead.contains(&(idx, state)) {
+            return false;
+        }
+
+        self.visited += 1;
+        if self.progress && self.visited.is_multiple_of(1_000_000) {
+            eprintln!(
+                "visited {} states, {} dead, at digit {}",
+                self.visited,
+                self.dead.len(),
+                idx + 1
+            );
+        }
+
+        for digit in digits {
+            let mut next = state;
+            // faulting digits are pruned like a non-zero z
+            if self.segments[idx].run(&mut next, &[*digit]).is_err() {
+                continue;
+            }
+
+            path.push(*digit);
+            if self.visit(idx + 1, next, digits, path) {
+                return true;
+            }
+            path.pop();
+        }
+
+        self.dead.insert((idx, state));
+        false
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_search() {
+        // z = b + c - a, the first digit stays in x across two segments
+        let programm = "inp x\ninp w\nadd z w\ninp y\nadd z y\nmul x -1\nadd z x"
+            .lines()
+            .map(Op::parse)
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+
+        let mut search = Search::new(&programm);
+        assert_eq!(search.largest(), Some(vec![9, 8, 1]));
+        assert_eq!(search.smallest(), Some(vec![2, 1, 1]));
+
+        // `mod` of a negative x faults for every digit but 9
+        let programm = "inp w\nadd x w\nadd x -9\nmod x 3\nadd z x"
+            .lines()
+            .map(Op::parse)
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+
+        let mut search = Search::new(&programm);
+        assert_eq!(search.smallest(), Some(vec![9]));
+
+        // the `mod` result is never read but still faults for digits below 5
+        let programm = "inp w\nadd x w\nadd x -5\nmod x 3\nmul x 0\nadd z 0"
+            .lines()
+            .map(Op::parse)
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+
+        let mut search = Search::new(&programm);
+        assert_eq!(search.largest(), Some(vec![9]));
+        assert_eq!(search.smallest(), Some(vec![5]));
+    }
+}
@@ -1,3 +1,4 @@
+use advent2021::check;
 use std::{collections::HashMap, error::Error, fmt::Display, iter::repeat};
 
 #[derive(Debug, Clone, PartialEq, Copy)]
@@ -42,15 +43,11 @@ impl Field {
             height += 1;
         }
 
-        if let Some(width) = width {
-            Some(Self {
-                width,
-                height,
-                points,
-            })
-        } else {
-            None
-        }
+        width.map(|width| Self {
+            width,
+            height,
+            points,
+        })
     }
 
     fn get(&self, x: usize, y: usize) -> Option<Cucumber> {
@@ -141,19 +138,28 @@ impl Display for Field {
                 }
             }
 
-            write!(f, "\n")?;
+            writeln!(f)?;
         }
         Ok(())
     }
 }
 
+pub(crate) fn solve(input: &str) -> Result<(usize, usize), Box<dyn Error>> {
+    let mut field = Field::parse(input).ok_or("Can't parse input")?;
+    let result_a = repeat(()).take_while(|_| field.step() > 0).count() + 1;
+
+    Ok((result_a, 0))
+}
+
 fn main() -> Result<(), Box<dyn Error>> {
     let filename = std::env::args().nth(1).ok_or("Invalid input")?;
     let input = std::fs::read_to_string(filename)?;
-    let mut field = Field::parse(&input).ok_or("Can't parse input")?;
-    let result_a = repeat(()).take_while(|_| field.step() > 0).count() + 1;
-    let result_b = 0;
 
+    if std::env::args().any(|a| a == "--check") {
+        return check::report(&check::grid(&input, |c| matches!(c, '>' | 'v' | '.')));
+    }
+
+    let (result_a, result_b) = solve(&input)?;
     println!("Task A: {}, Task B: {}", result_a, result_b);
 
     Ok(())
@@ -0,0 +1,196 @@
+use std::{
+    error::Error,
+    fmt,
+    path::PathBuf,
+    process::{exit, Command},
+};
+
+struct Day {
+    year: u32,
+    number: u32,
+    repl: bool,
+    check: bool,
+}
+
+// Unused constructors are expected when only a few days are compiled in.
+#[allow(dead_code)]
+impl Day {
+    const fn new(year: u32, number: u32) -> Self {
+        Self {
+            year,
+            number,
+            repl: false,
+            check: false,
+        }
+    }
+
+    const fn with_repl(self) -> Self {
+        Self { repl: true, ..self }
+    }
+
+    const fn with_check(self) -> Self {
+        Self {
+            check: true,
+            ..self
+        }
+    }
+
+    fn binary(&self) -> Result<PathBuf, Box<dyn Error>> {
+        let name = format!(
+            "{}-day{:02}{}",
+            self.year,
+            self.number,
+            std::env::consts::EXE_SUFFIX
+        );
+        let path = std::env::current_exe()?.with_file_name(name);
+
+        if path.exists() {
+            Ok(path)
+        } else {
+            Err(format!("{} not found, run `cargo build` first", path.display()).into())
+        }
+    }
+
+    fn input(&self) -> String {
+        format!("input/{}/day{:02}.txt", self.year, self.number)
+    }
+}
+
+impl fmt::Display for Day {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} day{:02}", self.year, self.number)
+    }
+}
+
+// Days are registered only when their feature is enabled, so the runner
+// never points at a binary that was not compiled.
+const DAYS: &[Day] = &[
+    #[cfg(feature = "2021-day01")]
+    Day::new(2021, 1),
+    #[cfg(feature = "2021-day02")]
+    Day::new(2021, 2),
+    #[cfg(feature = "2021-day03")]
+    Day::new(2021, 3),
+    #[cfg(feature = "2021-day04")]
+    Day::new(2021, 4).with_check(),
+    #[cfg(feature = "2021-day05")]
+    Day::new(2021, 5).with_check(),
+    #[cfg(feature = "2021-day06")]
+    Day::new(2021, 6),
+    #[cfg(feature = "2021-day07")]
+    Day::new(2021, 7),
+    #[cfg(feature = "2021-day08")]
+    Day::new(2021, 8).with_repl(),
+    #[cfg(feature = "2021-day09")]
+    Day::new(2021, 9).with_check(),
+    #[cfg(feature = "2021-day10")]
+    Day::new(2021, 10).with_check(),
+    #[cfg(feature = "2021-day11")]
+    Day::new(2021, 11).with_check(),
+    #[cfg(feature = "2021-day12")]
+    Day::new(2021, 12).with_repl(),
+    #[cfg(feature = "2021-day13")]
+    Day::new(2021, 13),
+    #[cfg(feature = "2021-day14")]
+    Day::new(2021, 14),
+    #[cfg(feature = "2021-day15")]
+    Day::new(2021, 15).with_check(),
+    #[cfg(feature = "2021-day16")]
+    Day::new(2021, 16),
+    #[cfg(feature = "2021-day17")]
+    Day::new(2021, 17),
+    #[cfg(feature = "2021-day18")]
+    Day::new(2021, 18).with_repl(),
+    #[cfg(feature = "2021-day19")]
+    Day::new(2021, 19),
+    #[cfg(feature = "2021-day20")]
+    Day::new(2021, 20),
+    #[cfg(feature = "2021-day21")]
+    Day::new(2021, 21),
+    #[cfg(feature = "2021-day22")]
+    Day::new(2021, 22),
+    #[cfg(feature = "2021-day23")]
+    Day::new(2021, 23),
+    #[cfg(feature = "2021-day24")]
+    Day::new(2021, 24).with_repl().with_check(),
+    #[cfg(feature = "2021-day25")]
+    Day::new(2021, 25).with_check(),
+];
+
+const USAGE: &str = "Usage: aoc list [year]
+       aoc run <year> <day> [input]
+       aoc repl <year> <day> [input]
+       aoc check <year> <day> [input]";
+
+fn parse_number(input: &str) -> Result<u32, Box<dyn Error>> {
+    input
+        .trim_start_matches("day")
+        .parse::<u32>()
+        .map_err(|_| format!("Invalid number: {}", input).into())
+}
+
+fn find_day(args: &mut impl Iterator<Item = String>) -> Result<&'static Day, Box<dyn Error>> {
+    let year = args.next().ok_or(USAGE).map(|y| parse_number(&y))??;
+    let number = args.next().ok_or(USAGE).map(|d| parse_number(&d))??;
+
+    DAYS.iter()
+        .find(|d| d.year == year && d.number == number)
+        .ok_or_else(|| {
+            let message = format!(
+                "{} day{:02} is not compiled in, enable its feature",
+                year, number
+            );
+            message.into()
+        })
+}
+
+fn run(day: &Day, input: Option<String>, flags: &[&str]) -> Result<(), Box<dyn Error>> {
+    let input = input.unwrap_or_else(|| day.input());
+    let status = Command::new(day.binary()?)
+        .arg(input)
+        .args(flags)
+        .status()?;
+
+    match status.code() {
+        Some(0) => Ok(()),
+        Some(code) => exit(code),
+        None => Err("Terminated by signal".into()),
+    }
+}
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let mut args = std::env::args().skip(1);
+    let command = args.next().ok_or(USAGE)?;
+
+    match command.as_str() {
+        "list" => {
+            let year = args.next().map(|y| parse_number(&y)).transpose()?;
+            for day in DAYS.iter().filter(|d| year.is_none_or(|y| d.year == y)) {
+                let repl = if day.repl { " [repl]" } else { "" };
+                let check = if day.check { " [check]" } else { "" };
+                println!("{}{}{}", day, repl, check);
+            }
+        }
+        "run" => {
+            let day = find_day(&mut args)?;
+            run(day, args.next(), &[])?;
+        }
+        "repl" => {
+            let day = find_day(&mut args)?;
+            if !day.repl {
+                return Err(format!("{} has no repl", day).into());
+            }
+            run(day, args.next(), &["--repl"])?;
+        }
+        "check" => {
+            let day = find_day(&mut args)?;
+            if !day.check {
+                return Err(format!("{} has no input check", day).into());
+            }
+            run(day, args.next(), &["--check"])?;
+        }
+        _ => return Err(USAGE.into()),
+    }
+
+    Ok(())
+}
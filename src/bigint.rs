@@ -0,0 +1,165 @@
+use std::{
+    fmt,
+    ops::{Add, Mul},
+};
+
+const BASE: u64 = 1 << 32;
+
+// Little-endian u32 limbs without trailing zero limbs.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BigUint {
+    limbs: Vec<u32>,
+}
+
+impl BigUint {
+    pub fn zero() -> Self {
+        Self { limbs: vec![] }
+    }
+
+    fn trim(mut self) -> Self {
+        while self.limbs.last() == Some(&0) {
+            self.limbs.pop();
+        }
+        self
+    }
+
+    // Bits are given from the most significant one.
+    pub fn from_bits<I>(bits: I) -> Self
+    where
+        I: IntoIterator<Item = bool>,
+        I::IntoIter: DoubleEndedIterator,
+    {
+        let mut limbs = vec![];
+
+        for (idx, bit) in bits.into_iter().rev().enumerate() {
+            if idx % 32 == 0 {
+                limbs.push(0);
+            }
+            if bit {
+                limbs[idx / 32] |= 1 << (idx % 32);
+            }
+        }
+
+        Self { limbs }.trim()
+    }
+
+    // Divides in place by a small divisor and returns the remainder.
+    fn div_rem_small(&mut self, divisor: u32) -> u32 {
+        let mut rem = 0u64;
+
+        for limb in self.limbs.iter_mut().rev() {
+            let current = rem * BASE + *limb as u64;
+            *limb = (current / divisor as u64) as u32;
+            rem = current % divisor as u64;
+        }
+
+        while self.limbs.last() == Some(&0) {
+            self.limbs.pop();
+        }
+
+        rem as u32
+    }
+}
+
+impl From<u64> for BigUint {
+    fn from(value: u64) -> Self {
+        Self {
+            limbs: vec![value as u32, (value >> 32) as u32],
+        }
+        .trim()
+    }
+}
+
+impl Add for &BigUint {
+    type Output = BigUint;
+
+    fn add(self, other: &BigUint) -> BigUint {
+        let len = self.limbs.len().max(other.limbs.len());
+        let mut limbs = Vec::with_capacity(len + 1);
+        let mut carry = 0u64;
+
+        for idx in 0..len {
+            let a = self.limbs.get(idx).copied().unwrap_or(0) as u64;
+            let b = other.limbs.get(idx).copied().unwrap_or(0) as u64;
+            let sum = a + b + carry;
+            limbs.push(sum as u32);
+            carry = sum >> 32;
+        }
+
+        limbs.push(carry as u32);
+        BigUint { limbs }.trim()
+    }
+}
+
+impl Mul for &BigUint {
+    type Output = BigUint;
+
+    fn mul(self, other: &BigUint) -> BigUint {
+        let mut limbs = vec![0u32; self.limbs.len() + other.limbs.len()];
+
+        for (i, a) in self.limbs.iter().enumerate() {
+            let mut carry = 0u64;
+
+            for (j, b) in other.limbs.iter().enumerate() {
+                let current = limbs[i + j] as u64 + *a as u64 * *b as u64 + carry;
+                limbs[i + j] = current as u32;
+                carry = current >> 32;
+            }
+
+            limbs[i + other.limbs.len()] = carry as u32;
+        }
+
+        BigUint { limbs }.trim()
+    }
+}
+
+impl fmt::Display for BigUint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.limbs.is_empty() {
+            return write!(f, "0");
+        }
+
+        let mut value = self.clone();
+        let mut chunks = vec![];
+        while !value.limbs.is_empty() {
+            chunks.push(value.div_rem_small(1_000_000_000));
+        }
+
+        let mut chunks = chunks.iter().rev();
+        if let Some(first) = chunks.next() {
+            write!(f, "{}", first)?;
+        }
+        for chunk in chunks {
+            write!(f, "{:09}", chunk)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_arithmetic() {
+        let a = BigUint::from(u64::MAX);
+        let b = BigUint::from(1);
+
+        assert_eq!((&a + &b).to_string(), "18446744073709551616");
+        assert_eq!(
+            (&a * &a).to_string(),
+            "340282366920938463426481119284349108225"
+        );
+        assert_eq!(BigUint::zero().to_string(), "0");
+        assert_eq!((&a * &BigUint::zero()), BigUint::zero());
+
+        let bits = "1".repeat(64).chars().map(|c| c == '1').collect::<Vec<_>>();
+        assert_eq!(BigUint::from_bits(bits), a);
+        assert_eq!(
+            BigUint::from_bits([false, true, true, false]),
+            BigUint::from(6)
+        );
+        assert_eq!(BigUint::from_bits([false; 40]), BigUint::zero());
+    }
+}
@@ -0,0 +1,83 @@
+use std::{error::Error, fmt};
+
+#[derive(Debug, PartialEq)]
+pub struct Violation {
+    line: usize,
+    message: String,
+}
+
+impl Violation {
+    pub fn new(line: usize, message: impl Into<String>) -> Self {
+        let message = message.into();
+        Self { line, message }
+    }
+}
+
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+// Checks a rectangular character grid: every row must be as wide as the
+// first one and contain only characters accepted by `valid`.
+pub fn grid<F>(input: &str, valid: F) -> Vec<Violation>
+where
+    F: Fn(char) -> bool,
+{
+    let mut violations = vec![];
+    let width = match input.lines().next() {
+        Some(line) => line.chars().count(),
+        None => return vec![Violation::new(1, "empty input")],
+    };
+
+    for (idx, line) in input.lines().enumerate() {
+        let size = line.chars().count();
+        if size != width {
+            let message = format!("ragged row: expected {} columns, found {}", width, size);
+            violations.push(Violation::new(idx + 1, message));
+        }
+
+        for (col, c) in line.chars().enumerate().filter(|(_, c)| !valid(*c)) {
+            let message = format!("unexpected character {:?} at column {}", c, col + 1);
+            violations.push(Violation::new(idx + 1, message));
+        }
+    }
+
+    violations
+}
+
+pub fn report(violations: &[Violation]) -> Result<(), Box<dyn Error>> {
+    for violation in violations {
+        println!("{}", violation);
+    }
+
+    match violations.len() {
+        0 => {
+            println!("OK");
+            Ok(())
+        }
+        n => Err(format!("{} violation(s) found", n).into()),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_grid() {
+        let violations = grid("123\n45\n7x9", |c| c.is_ascii_digit());
+        let expected = vec![
+            Violation::new(2, "ragged row: expected 3 columns, found 2"),
+            Violation::new(3, "unexpected character 'x' at column 2"),
+        ];
+
+        assert_eq!(violations, expected);
+    }
+
+    #[test]
+    fn test_grid_empty() {
+        assert_eq!(grid("", |_| true), vec![Violation::new(1, "empty input")]);
+    }
+}
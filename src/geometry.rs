@@ -0,0 +1,236 @@
+use std::{
+    fmt,
+    ops::{Add, Sub},
+};
+
+fn segments(amin: i64, amax: i64, bmin: i64, bmax: i64) -> impl Iterator<Item = (i64, i64)> {
+    let p0 = amin.min(bmin);
+    let p1 = amin.max(bmin);
+    let p2 = amax.min(bmax);
+    let p3 = amax.max(bmax);
+
+    let left = (p0, p1 - 1);
+    let middle = (p1, p2);
+    let right = (p2 + 1, p3);
+
+    vec![left, middle, right].into_iter().filter(|r| r.0 <= r.1)
+}
+
+fn parts(a: &Cuboid, b: &Cuboid) -> impl Iterator<Item = Cuboid> {
+    let all = segments(a.zmin, a.zmax, b.zmin, b.zmax)
+        .flat_map(|(za, zb)| {
+            segments(a.ymin, a.ymax, b.ymin, b.ymax).flat_map(move |(ya, yb)| {
+                segments(a.xmin, a.xmax, b.xmin, b.xmax)
+                    .map(move |(xa, xb)| Cuboid::new(xa, xb, ya, yb, za, zb))
+            })
+        })
+        .collect::<Vec<_>>();
+
+    all.into_iter()
+}
+
+// return parts of A not contains in B
+pub fn not(a: &Cuboid, b: &Cuboid) -> impl Iterator<Item = Cuboid> {
+    if a.intersect(b) {
+        let collected = parts(a, b)
+            .filter(|part| part.intersect(a) && !part.intersect(b))
+            .collect::<Vec<_>>();
+
+        collected.into_iter()
+    } else {
+        vec![a.clone()].into_iter()
+    }
+}
+
+#[derive(Debug, Hash, Eq, PartialEq, Clone)]
+pub struct Cuboid {
+    xmin: i64,
+    xmax: i64,
+    ymin: i64,
+    ymax: i64,
+    zmin: i64,
+    zmax: i64,
+}
+
+impl Cuboid {
+    pub fn new(xmin: i64, xmax: i64, ymin: i64, ymax: i64, zmin: i64, zmax: i64) -> Self {
+        Self {
+            xmin,
+            xmax,
+            ymin,
+            ymax,
+            zmin,
+            zmax,
+        }
+    }
+
+    pub fn intersect(&self, b: &Cuboid) -> bool {
+        let x = b.xmin <= self.xmax && b.xmax >= self.xmin;
+        let y = b.ymin <= self.ymax && b.ymax >= self.ymin;
+        let z = b.zmin <= self.zmax && b.zmax >= self.zmin;
+
+        x && y && z
+    }
+
+    pub fn size(&self) -> i64 {
+        (self.xmax - self.xmin + 1) * (self.ymax - self.ymin + 1) * (self.zmax - self.zmin + 1)
+    }
+
+    pub fn limit(&self, limit: i64) -> Option<Cuboid> {
+        let xmin = self.xmin.max(-limit);
+        let xmax = self.xmax.min(limit);
+
+        let ymin = self.ymin.max(-limit);
+        let ymax = self.ymax.min(limit);
+
+        let zmin = self.zmin.max(-limit);
+        let zmax = self.zmax.min(limit);
+
+        if self.xmin >= -limit
+            && self.xmax <= limit
+            && self.ymin >= -limit
+            && self.ymax <= limit
+            && self.zmin >= -limit
+            && self.zmax <= limit
+        {
+            Some(Self {
+                xmin,
+                xmax,
+                ymin,
+                ymax,
+                zmin,
+                zmax,
+            })
+        } else {
+            None
+        }
+    }
+}
+
+#[derive(Debug, Hash, Eq, PartialEq, Clone, Copy, Ord, PartialOrd)]
+pub struct Point3 {
+    pub x: i64,
+    pub y: i64,
+    pub z: i64,
+}
+
+impl Point3 {
+    pub fn new(x: i64, y: i64, z: i64) -> Self {
+        Self { x, y, z }
+    }
+
+    pub fn manhattan(&self, to: Point3) -> i64 {
+        (self.x - to.x).abs() + (self.y - to.y).abs() + (self.z - to.z).abs()
+    }
+
+    // squared euclidean length, stays the same under any rotation
+    pub fn norm(&self) -> i64 {
+        self.x * self.x + self.y * self.y + self.z * self.z
+    }
+}
+
+impl Add for Point3 {
+    type Output = Point3;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Point3::new(self.x + rhs.x, self.y + rhs.y, self.z + rhs.z)
+    }
+}
+
+impl Sub for Point3 {
+    type Output = Point3;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Point3::new(self.x - rhs.x, self.y - rhs.y, self.z - rhs.z)
+    }
+}
+
+impl fmt::Display for Point3 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{},{},{}", self.x, self.y, self.z)
+    }
+}
+
+// One of the 24 orientations of a cube, stored as a rotation matrix.
+#[derive(Debug, Hash, Eq, PartialEq, Clone, Copy)]
+pub struct Rotation {
+    matrix: [[i64; 3]; 3],
+}
+
+impl Rotation {
+    pub fn identity() -> Self {
+        let matrix = [[1, 0, 0], [0, 1, 0], [0, 0, 1]];
+        Self { matrix }
+    }
+
+    pub fn all() -> Vec<Rotation> {
+        let axes = [
+            [0, 1, 2],
+            [0, 2, 1],
+            [1, 0, 2],
+            [1, 2, 0],
+            [2, 0, 1],
+            [2, 1, 0],
+        ];
+        let mut result = vec![];
+
+        for axis in axes.iter() {
+            for signs in 0..8 {
+                let mut matrix = [[0; 3]; 3];
+                for (row, col) in axis.iter().enumerate() {
+                    matrix[row][*col] = if signs & (1 << row) == 0 { 1 } else { -1 };
+                }
+
+                let rotation = Self { matrix };
+                if rotation.determinant() == 1 {
+                    result.push(rotation);
+                }
+            }
+        }
+
+        result
+    }
+
+    pub fn apply(&self, p: Point3) -> Point3 {
+        let m = &self.matrix;
+        Point3::new(
+            m[0][0] * p.x + m[0][1] * p.y + m[0][2] * p.z,
+            m[1][0] * p.x + m[1][1] * p.y + m[1][2] * p.z,
+            m[2][0] * p.x + m[2][1] * p.y + m[2][2] * p.z,
+        )
+    }
+
+    fn determinant(&self) -> i64 {
+        let m = &self.matrix;
+        m[0][0] * (m[1][1] * m[2][2] - m[1][2] * m[2][1])
+            - m[0][1] * (m[1][0] * m[2][2] - m[1][2] * m[2][0])
+            + m[0][2] * (m[1][0] * m[2][1] - m[1][1] * m[2][0])
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_rotations() {
+        let rotations = Rotation::all();
+        let p = Point3::new(1, 2, 3);
+        let images = rotations.iter().map(|r| r.apply(p)).collect::<HashSet<_>>();
+
+        assert_eq!(rotations.len(), 24);
+        assert_eq!(images.len(), 24);
+        assert!(rotations.contains(&Rotation::identity()));
+        assert!(images.iter().all(|i| i.norm() == p.norm()));
+    }
+
+    #[test]
+    fn test_not() {
+        let a = Cuboid::new(0, 2, 0, 2, 0, 2);
+        let b = Cuboid::new(1, 1, 1, 1, 1, 1);
+        let size: i64 = not(&a, &b).map(|c| c.size()).sum();
+
+        assert_eq!(size, 26);
+    }
+}
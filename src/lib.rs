@@ -0,0 +1,4 @@
+pub mod bigint;
+pub mod check;
+pub mod geometry;
+pub mod repl;
@@ -0,0 +1,68 @@
+use std::{
+    error::Error,
+    io::{self, BufRead, Write},
+};
+
+pub trait Session {
+    fn help(&self) -> &str;
+    fn eval(&mut self, command: &str, args: &[&str]) -> Result<String, Box<dyn Error>>;
+}
+
+pub fn arg<T>(args: &[&str], idx: usize) -> Result<T, Box<dyn Error>>
+where
+    T: std::str::FromStr,
+{
+    let raw = args
+        .get(idx)
+        .ok_or_else(|| format!("Missing argument #{}", idx + 1))?;
+
+    raw.parse::<T>()
+        .map_err(|_| format!("Invalid argument: {}", raw).into())
+}
+
+pub fn arg_or<T>(args: &[&str], idx: usize, default: T) -> Result<T, Box<dyn Error>>
+where
+    T: std::str::FromStr,
+{
+    if args.len() > idx {
+        arg(args, idx)
+    } else {
+        Ok(default)
+    }
+}
+
+pub fn run<S>(session: &mut S) -> Result<(), Box<dyn Error>>
+where
+    S: Session,
+{
+    let stdin = io::stdin();
+    let mut lines = stdin.lock().lines();
+
+    loop {
+        print!("> ");
+        io::stdout().flush()?;
+
+        let line = match lines.next() {
+            Some(line) => line?,
+            None => break,
+        };
+
+        let mut words = line.split_whitespace();
+        let command = match words.next() {
+            Some(command) => command,
+            None => continue,
+        };
+        let args = words.collect::<Vec<_>>();
+
+        match command {
+            "quit" | "exit" => break,
+            "help" => println!("{}", session.help()),
+            _ => match session.eval(command, &args) {
+                Ok(output) => println!("{}", output),
+                Err(e) => println!("Error: {}", e),
+            },
+        }
+    }
+
+    Ok(())
+}
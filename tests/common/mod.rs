@@ -0,0 +1,5 @@
+// The helper is unused when no day feature is enabled.
+#[allow(dead_code)]
+pub fn fixture(path: &str) -> String {
+    std::fs::read_to_string(path).expect("Can't read fixture")
+}
@@ -0,0 +1,197 @@
+mod common;
+
+#[cfg(feature = "2021-day01")]
+#[allow(dead_code)]
+#[path = "../src/2021/day01/main.rs"]
+mod day01;
+
+#[cfg(feature = "2021-day02")]
+#[allow(dead_code)]
+#[path = "../src/2021/day02/main.rs"]
+mod day02;
+
+#[cfg(feature = "2021-day03")]
+#[allow(dead_code)]
+#[path = "../src/2021/day03/main.rs"]
+mod day03;
+
+#[cfg(feature = "2021-day04")]
+#[allow(dead_code)]
+#[path = "../src/2021/day04/main.rs"]
+mod day04;
+
+#[cfg(feature = "2021-day05")]
+#[allow(dead_code)]
+#[path = "../src/2021/day05/main.rs"]
+mod day05;
+
+#[cfg(feature = "2021-day06")]
+#[allow(dead_code)]
+#[path = "../src/2021/day06/main.rs"]
+mod day06;
+
+#[cfg(feature = "2021-day07")]
+#[allow(dead_code)]
+#[path = "../src/2021/day07/main.rs"]
+mod day07;
+
+#[cfg(feature = "2021-day08")]
+#[allow(dead_code)]
+#[path = "../src/2021/day08/main.rs"]
+mod day08;
+
+#[cfg(feature = "2021-day09")]
+#[allow(dead_code)]
+#[path = "../src/2021/day09/main.rs"]
+mod day09;
+
+#[cfg(feature = "2021-day10")]
+#[allow(dead_code)]
+#[path = "../src/2021/day10/main.rs"]
+mod day10;
+
+#[cfg(feature = "2021-day11")]
+#[allow(dead_code)]
+#[path = "../src/2021/day11/main.rs"]
+mod day11;
+
+#[cfg(feature = "2021-day12")]
+#[allow(dead_code)]
+#[path = "../src/2021/day12/main.rs"]
+mod day12;
+
+#[cfg(feature = "2021-day13")]
+#[allow(dead_code)]
+#[path = "../src/2021/day13/main.rs"]
+mod day13;
+
+#[cfg(feature = "2021-day14")]
+#[allow(dead_code)]
+#[path = "../src/2021/day14/main.rs"]
+mod day14;
+
+#[cfg(feature = "2021-day15")]
+#[allow(dead_code)]
+#[path = "../src/2021/day15/main.rs"]
+mod day15;
+
+#[cfg(feature = "2021-day16")]
+#[allow(dead_code)]
+#[path = "../src/2021/day16/main.rs"]
+mod day16;
+
+#[cfg(feature = "2021-day17")]
+#[allow(dead_code)]
+#[path = "../src/2021/day17/main.rs"]
+mod day17;
+
+#[cfg(feature = "2021-day18")]
+#[allow(dead_code)]
+#[path = "../src/2021/day18/main.rs"]
+mod day18;
+
+#[cfg(feature = "2021-day19")]
+#[allow(dead_code)]
+#[path = "../src/2021/day19/main.rs"]
+mod day19;
+
+#[cfg(feature = "2021-day20")]
+#[allow(dead_code)]
+#[path = "../src/2021/day20/main.rs"]
+mod day20;
+
+#[cfg(feature = "2021-day21")]
+#[allow(dead_code)]
+#[path = "../src/2021/day21/main.rs"]
+mod day21;
+
+#[cfg(feature = "2021-day22")]
+#[allow(dead_code)]
+#[path = "../src/2021/day22/main.rs"]
+mod day22;
+
+#[cfg(feature = "2021-day23")]
+#[allow(dead_code)]
+#[path = "../src/2021/day23/main.rs"]
+mod day23;
+
+#[cfg(feature = "2021-day24")]
+#[allow(dead_code)]
+#[path = "../src/2021/day24/main.rs"]
+mod day24;
+
+#[cfg(feature = "2021-day25")]
+#[allow(dead_code)]
+#[path = "../src/2021/day25/main.rs"]
+mod day25;
+
+macro_rules! example {
+    (@part $answers:ident, A) => {
+        $answers.0
+    };
+    (@part $answers:ident, B) => {
+        $answers.1
+    };
+    ($name:ident, $feature:literal, $($part:ident = $expected:literal),+) => {
+        example!(
+            $name,
+            $name,
+            $feature,
+            concat!("input/2021/", stringify!($name), "-demo.txt"),
+            $($part = $expected),+
+        );
+    };
+    ($name:ident, $day:ident, $feature:literal, $fixture:expr, $($part:ident = $expected:literal),+) => {
+        #[cfg(feature = $feature)]
+        #[test]
+        fn $name() {
+            let answers = $day::solve(&common::fixture($fixture)).expect("Can't solve example");
+            $(
+                let answer = example!(@part answers, $part).to_string();
+                assert_eq!(answer, $expected, "part {}", stringify!($part));
+            )+
+        }
+    };
+}
+
+example!(day01, "2021-day01", A = "7", B = "5");
+example!(day02, "2021-day02", A = "150", B = "900");
+example!(day03, "2021-day03", A = "198", B = "230");
+example!(day04, "2021-day04", A = "4512", B = "1924");
+example!(day05, "2021-day05", A = "5", B = "12");
+example!(day06, "2021-day06", A = "5934", B = "26984457539");
+example!(day07, "2021-day07", A = "37", B = "168");
+example!(day08, "2021-day08", A = "26", B = "61229");
+example!(day09, "2021-day09", A = "15", B = "1134");
+example!(day10, "2021-day10", A = "26397", B = "288957");
+example!(day11, "2021-day11", A = "1656", B = "195");
+example!(day12, "2021-day12", A = "10", B = "36");
+example!(day13, "2021-day13", A = "17");
+example!(day14, "2021-day14", A = "1588", B = "2188189693529");
+example!(day15, "2021-day15", A = "40", B = "315");
+example!(day16, "2021-day16", A = "31");
+example!(
+    day16_b,
+    day16,
+    "2021-day16",
+    "input/2021/day16-demo-b.txt",
+    B = "1"
+);
+example!(day17, "2021-day17", A = "45", B = "112");
+example!(day18, "2021-day18", A = "4140", B = "3993");
+example!(day19, "2021-day19", A = "79", B = "3621");
+example!(day20, "2021-day20", A = "35", B = "3351");
+example!(day21, "2021-day21", A = "739785", B = "444356092776315");
+example!(day22, "2021-day22", A = "474140", B = "2758514936282235");
+example!(day23, "2021-day23", A = "12521", B = "44169");
+example!(day25, "2021-day25", A = "58");
+
+#[cfg(feature = "2021-day13")]
+#[test]
+fn day13_code() {
+    let (_, field) = day13::solve(&common::fixture("input/2021/day13-demo.txt")).unwrap();
+    let code = field.to_string();
+    let expected = "█████\n█   █\n█   █\n█   █\n█████\n";
+
+    assert!(code.starts_with(expected), "unexpected code:\n{}", code);
+}